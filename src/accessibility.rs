@@ -0,0 +1,167 @@
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+
+use crate::events::{PaddleHit, PointScored, WallBounce};
+use crate::locale::Locale;
+use crate::schedule::{GameState, InGameSet};
+use crate::settings::Settings;
+use crate::theme::{ActiveTheme, Themed};
+
+pub const OUTLINE_WIDTH: f32 = 3.;
+// Just behind the outlined entity, relative to it
+const OUTLINE_Z: f32 = -0.05;
+
+const CAPTION_SECONDS: f32 = 1.;
+const CAPTION_FONT_SIZE: f32 = 32.;
+
+pub struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CaptionTimer>()
+            .add_systems(Startup, spawn_caption)
+            .add_systems(
+                FixedUpdate,
+                caption_sounds
+                    .in_set(InGameSet::EntityUpdates)
+                    // Only what the audio plays, which is silent in attract mode
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(Update, (show_outlines, hide_caption));
+    }
+}
+
+/// A high-contrast outline, spawned as a child of what it outlines and only shown
+/// when the setting is on.
+#[derive(Component)]
+pub struct Outline;
+
+/// An outline for a sprite of `size`.
+pub fn outline_sprite(size: Vec2) -> impl Bundle {
+    (
+        SpriteBundle {
+            sprite: Sprite {
+                custom_size: Some(size + 2. * OUTLINE_WIDTH),
+                ..default()
+            },
+            transform: Transform::from_xyz(0., 0., OUTLINE_Z),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        Outline,
+        Themed::Outline,
+    )
+}
+
+/// An outline for a mesh, given a copy of it already grown by `OUTLINE_WIDTH`.
+pub fn outline_mesh(mesh: Handle<Mesh>, material: Handle<ColorMaterial>) -> impl Bundle {
+    (
+        MaterialMesh2dBundle {
+            mesh: mesh.into(),
+            material,
+            transform: Transform::from_xyz(0., 0., OUTLINE_Z),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        Outline,
+        Themed::Outline,
+    )
+}
+
+// Shows or hides every outline when the setting changes, and new ones as they spawn
+fn show_outlines(
+    settings: Res<Settings>,
+    mut outline_query: Query<(Ref<Outline>, &mut Visibility)>,
+) {
+    let shown = match settings.outlines {
+        true => Visibility::Inherited,
+        false => Visibility::Hidden,
+    };
+    for (outline, mut visibility) in &mut outline_query {
+        if settings.is_changed() || outline.is_added() {
+            *visibility = shown;
+        }
+    }
+}
+
+#[derive(Component)]
+struct Caption;
+
+#[derive(Resource, Default, Deref, DerefMut)]
+struct CaptionTimer(Timer);
+
+// Captions sit at the bottom of the screen, over the game
+fn spawn_caption(mut commands: Commands) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(40.),
+                width: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle {
+                    style: Style {
+                        padding: UiRect::axes(Val::Px(12.), Val::Px(4.)),
+                        ..default()
+                    },
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+                Caption,
+            ));
+        });
+}
+
+fn caption_sounds(
+    settings: Res<Settings>,
+    theme: Res<ActiveTheme>,
+    locale: Res<Locale>,
+    mut paddle_hit_events: EventReader<PaddleHit>,
+    mut wall_bounce_events: EventReader<WallBounce>,
+    mut point_events: EventReader<PointScored>,
+    mut caption_query: Query<(&mut Text, &mut BackgroundColor, &mut Visibility), With<Caption>>,
+    mut timer: ResMut<CaptionTimer>,
+) {
+    let bounced = paddle_hit_events.read().count() + wall_bounce_events.read().count() > 0;
+    let scored = point_events.read().count() > 0;
+    // The goal is the more important of the two
+    let caption = match (scored, bounced) {
+        (true, _) => "caption.goal",
+        (false, true) => "caption.bounce",
+        (false, false) => return,
+    };
+    if !settings.captions {
+        return;
+    }
+
+    for (mut text, mut background, mut visibility) in &mut caption_query {
+        *text = Text::from_section(
+            locale.get(caption),
+            TextStyle {
+                font: theme.font.clone(),
+                font_size: CAPTION_FONT_SIZE * settings.text_scale,
+                color: theme.text,
+            },
+        );
+        *background = theme.toast.into();
+        *visibility = Visibility::Inherited;
+    }
+    **timer = Timer::from_seconds(CAPTION_SECONDS, TimerMode::Once);
+}
+
+fn hide_caption(
+    mut timer: ResMut<CaptionTimer>,
+    mut caption_query: Query<&mut Visibility, With<Caption>>,
+    time: Res<Time>,
+) {
+    if timer.tick(time.delta()).just_finished() {
+        for mut visibility in &mut caption_query {
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
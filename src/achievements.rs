@@ -0,0 +1,301 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::controller::Difficulty;
+use crate::events::{MatchEnded, PaddleHit, PointScored};
+use crate::locale::Locale;
+use crate::paddle::{Paddle, Player};
+use crate::schedule::{GameState, InGameSet};
+use crate::score::Score;
+use crate::settings::Settings;
+use crate::stats::now;
+use crate::theme::ActiveTheme;
+use crate::{storage, Side};
+
+pub const ACHIEVEMENTS_FILE: &str = "achievements.ron";
+const ACHIEVEMENTS_VERSION: u32 = 1;
+
+const MARATHON_RALLY_HITS: u32 = 50;
+// Relative hit offset past which a shot counts as played off the edge of the paddle
+const EDGE_HIT_OFFSET: f32 = 0.4;
+
+const TOAST_SECONDS: f32 = 4.;
+
+pub struct AchievementsPlugin;
+
+impl Plugin for AchievementsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_achievements())
+            .init_resource::<AchievementTracker>()
+            .add_event::<AchievementEarned>()
+            .add_event::<AchievementUnlocked>()
+            .add_systems(Startup, spawn_toast_container)
+            .add_systems(OnEnter(GameState::Reset), reset_tracker)
+            .add_systems(
+                FixedUpdate,
+                (track_rallies, check_match_result)
+                    .chain()
+                    .after(InGameSet::CollisionDetection)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(Update, (unlock_achievements, show_toasts).chain());
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Achievement {
+    /// Win a match without conceding a point.
+    Flawless,
+    /// Keep a single rally going for 50 paddle hits.
+    Marathon,
+    /// Beat the CPU on Impossible.
+    GiantSlayer,
+    /// Score with a shot played off the very edge of your paddle.
+    EdgeOfGlory,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 4] = [
+        Achievement::Flawless,
+        Achievement::Marathon,
+        Achievement::GiantSlayer,
+        Achievement::EdgeOfGlory,
+    ];
+
+    pub fn name_key(&self) -> &'static str {
+        match self {
+            Achievement::Flawless => "achievement.flawless.name",
+            Achievement::Marathon => "achievement.marathon.name",
+            Achievement::GiantSlayer => "achievement.giant_slayer.name",
+            Achievement::EdgeOfGlory => "achievement.edge_of_glory.name",
+        }
+    }
+
+    pub fn description_key(&self) -> &'static str {
+        match self {
+            Achievement::Flawless => "achievement.flawless.description",
+            Achievement::Marathon => "achievement.marathon.description",
+            Achievement::GiantSlayer => "achievement.giant_slayer.description",
+            Achievement::EdgeOfGlory => "achievement.edge_of_glory.description",
+        }
+    }
+}
+
+/// Sent when the player earns an achievement for the first time.
+#[derive(Event)]
+pub struct AchievementUnlocked(pub Achievement);
+
+// Earned this tick, and not yet checked against what was already unlocked
+#[derive(Event)]
+struct AchievementEarned(Achievement);
+
+/// Every achievement the player has unlocked, with the Unix time it was unlocked at.
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct UnlockedAchievements(pub BTreeMap<Achievement, u64>);
+
+#[derive(Serialize, Deserialize)]
+struct AchievementsFile {
+    version: u32,
+    unlocked: UnlockedAchievements,
+}
+
+fn load_achievements() -> UnlockedAchievements {
+    match storage::load::<AchievementsFile>(ACHIEVEMENTS_FILE) {
+        Some(file) if file.version == ACHIEVEMENTS_VERSION => file.unlocked,
+        Some(file) => {
+            warn!(
+                "Ignoring achievements from unsupported version {}",
+                file.version
+            );
+            UnlockedAchievements::default()
+        }
+        None => UnlockedAchievements::default(),
+    }
+}
+
+#[derive(Resource, Default)]
+struct AchievementTracker {
+    rally: u32,
+    last_hit: Option<PaddleHit>,
+}
+
+fn reset_tracker(mut tracker: ResMut<AchievementTracker>) {
+    *tracker = AchievementTracker::default();
+}
+
+fn player_side(player_query: &Query<&Paddle, With<Player>>) -> Side {
+    player_query
+        .get_single()
+        .map(|paddle| paddle.side)
+        .unwrap_or(Side::Right)
+}
+
+fn track_rallies(
+    mut tracker: ResMut<AchievementTracker>,
+    player_query: Query<&Paddle, With<Player>>,
+    mut paddle_hit_events: EventReader<PaddleHit>,
+    mut point_events: EventReader<PointScored>,
+    mut earned_events: EventWriter<AchievementEarned>,
+) {
+    for hit in paddle_hit_events.read() {
+        tracker.rally += 1;
+        tracker.last_hit = Some(*hit);
+        if tracker.rally == MARATHON_RALLY_HITS {
+            earned_events.send(AchievementEarned(Achievement::Marathon));
+        }
+    }
+
+    let player = player_side(&player_query);
+    for point in point_events.read() {
+        if let Some(hit) = tracker.last_hit {
+            if point.scorer == player
+                && hit.side == player
+                && hit.hit_offset.abs() >= EDGE_HIT_OFFSET
+            {
+                earned_events.send(AchievementEarned(Achievement::EdgeOfGlory));
+            }
+        }
+        tracker.rally = 0;
+        tracker.last_hit = None;
+    }
+}
+
+fn check_match_result(
+    mut match_ended_events: EventReader<MatchEnded>,
+    mut earned_events: EventWriter<AchievementEarned>,
+    scoreboard: Res<Score>,
+    player_query: Query<&Paddle, With<Player>>,
+    opponent_query: Query<&Difficulty, (With<Paddle>, Without<Player>)>,
+) {
+    let player = player_side(&player_query);
+    for event in match_ended_events.read() {
+        if event.winner != player {
+            continue;
+        }
+        let conceded = match player {
+            Side::Left => scoreboard.right,
+            Side::Right => scoreboard.left,
+        };
+        if conceded == 0 {
+            earned_events.send(AchievementEarned(Achievement::Flawless));
+        }
+        if opponent_query.get_single().ok() == Some(&Difficulty::Impossible) {
+            earned_events.send(AchievementEarned(Achievement::GiantSlayer));
+        }
+    }
+}
+
+fn unlock_achievements(
+    mut earned_events: EventReader<AchievementEarned>,
+    mut unlocked_events: EventWriter<AchievementUnlocked>,
+    mut unlocked: ResMut<UnlockedAchievements>,
+) {
+    let mut changed = false;
+    for AchievementEarned(achievement) in earned_events.read() {
+        if unlocked.0.contains_key(achievement) {
+            continue;
+        }
+        unlocked.0.insert(*achievement, now());
+        unlocked_events.send(AchievementUnlocked(*achievement));
+        changed = true;
+    }
+
+    if changed {
+        let file = AchievementsFile {
+            version: ACHIEVEMENTS_VERSION,
+            unlocked: unlocked.clone(),
+        };
+        if let Err(error) = storage::save(ACHIEVEMENTS_FILE, &file) {
+            error!("Failed to save achievements: {error}");
+        }
+    }
+}
+
+#[derive(Component)]
+struct ToastContainer;
+
+#[derive(Component, Deref, DerefMut)]
+struct Toast(Timer);
+
+// Toasts stack in the top right corner, over both the game and the menu
+fn spawn_toast_container(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(20.),
+                right: Val::Px(20.),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::End,
+                ..default()
+            },
+            z_index: ZIndex::Global(10),
+            ..default()
+        },
+        ToastContainer,
+    ));
+}
+
+fn show_toasts(
+    mut commands: Commands,
+    theme: Res<ActiveTheme>,
+    settings: Res<Settings>,
+    locale: Res<Locale>,
+    mut unlocked_events: EventReader<AchievementUnlocked>,
+    container_query: Query<Entity, With<ToastContainer>>,
+    mut toast_query: Query<(Entity, &mut Toast)>,
+    time: Res<Time>,
+) {
+    for (entity, mut toast) in &mut toast_query {
+        if toast.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    let Ok(container) = container_query.get_single() else {
+        return;
+    };
+    for AchievementUnlocked(achievement) in unlocked_events.read() {
+        let toast = commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        margin: UiRect::bottom(Val::Px(10.)),
+                        padding: UiRect::all(Val::Px(12.)),
+                        border: UiRect::all(Val::Px(3.)),
+                        ..default()
+                    },
+                    background_color: theme.toast.into(),
+                    border_color: theme.accent.into(),
+                    ..default()
+                },
+                Toast(Timer::from_seconds(TOAST_SECONDS, TimerMode::Once)),
+            ))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    locale.format(
+                        "achievements.toast",
+                        &[("name", &locale.get(achievement.name_key()))],
+                    ),
+                    TextStyle {
+                        font: theme.font.clone(),
+                        font_size: 28. * settings.text_scale,
+                        color: theme.accent,
+                    },
+                ));
+                parent.spawn(TextBundle::from_section(
+                    locale.get(achievement.description_key()),
+                    TextStyle {
+                        font: theme.font.clone(),
+                        font_size: 20. * settings.text_scale,
+                        color: theme.text,
+                    },
+                ));
+            })
+            .id();
+        commands.entity(container).add_child(toast);
+    }
+}
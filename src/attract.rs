@@ -0,0 +1,215 @@
+use bevy::{
+    ecs::system::RunSystemOnce,
+    input::{gamepad::GamepadButton, mouse::MouseMotion},
+    prelude::*,
+};
+
+use crate::ball::reset_ball;
+use crate::controller::{Controller, CpuController};
+use crate::events::MatchEnded;
+use crate::locale::Locale;
+use crate::paddle::{reset_paddles, Paddle};
+use crate::save::MatchSnapshot;
+use crate::schedule::{GameState, InGameSet};
+use crate::score::reset_scores;
+use crate::settings::Settings;
+use crate::theme::ActiveTheme;
+
+pub struct AttractPlugin;
+
+impl Plugin for AttractPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AttractConfig>()
+            .init_resource::<IdleTimer>()
+            .add_systems(OnEnter(GameState::Menu), reset_idle_timer)
+            .add_systems(Update, start_attract.run_if(in_state(GameState::Menu)))
+            .add_systems(
+                OnEnter(GameState::Attract),
+                (suspend_match, hand_paddles_to_cpu, setup_title).chain(),
+            )
+            .add_systems(Update, end_attract.run_if(in_state(GameState::Attract)))
+            // The demo starts over rather than ending
+            .add_systems(
+                FixedUpdate,
+                (reset_ball, reset_paddles, reset_scores)
+                    .chain()
+                    .after(InGameSet::EntityUpdates)
+                    .run_if(in_state(GameState::Attract).and_then(on_event::<MatchEnded>())),
+            )
+            .add_systems(OnExit(GameState::Attract), (resume_match, teardown_title));
+    }
+}
+
+/// How long the menu has to sit untouched before the demo match starts.
+#[derive(Resource)]
+pub struct AttractConfig {
+    pub idle_seconds: f32,
+}
+
+impl Default for AttractConfig {
+    fn default() -> Self {
+        Self { idle_seconds: 20. }
+    }
+}
+
+#[derive(Resource, Default, Deref, DerefMut)]
+struct IdleTimer(Timer);
+
+#[derive(Component)]
+struct AttractItem;
+
+/// The match that was in progress before the demo took over the play area.
+#[derive(Resource)]
+pub struct SuspendedMatch {
+    pub snapshot: MatchSnapshot,
+    controllers: Vec<(Entity, Controller)>,
+}
+
+fn any_input(
+    keyboard: &ButtonInput<KeyCode>,
+    mouse_buttons: &ButtonInput<MouseButton>,
+    gamepad_buttons: &ButtonInput<GamepadButton>,
+    mouse_motion: &mut EventReader<MouseMotion>,
+) -> bool {
+    let moved = mouse_motion.read().count() > 0;
+    moved
+        || keyboard.get_just_pressed().next().is_some()
+        || mouse_buttons.get_just_pressed().next().is_some()
+        || gamepad_buttons.get_just_pressed().next().is_some()
+}
+
+fn reset_idle_timer(mut idle_timer: ResMut<IdleTimer>, config: Res<AttractConfig>) {
+    **idle_timer = Timer::from_seconds(config.idle_seconds, TimerMode::Once);
+}
+
+fn start_attract(
+    mut idle_timer: ResMut<IdleTimer>,
+    mut game_state: ResMut<NextState<GameState>>,
+    time: Res<Time>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut mouse_motion: EventReader<MouseMotion>,
+) {
+    if any_input(
+        &keyboard,
+        &mouse_buttons,
+        &gamepad_buttons,
+        &mut mouse_motion,
+    ) {
+        idle_timer.reset();
+    }
+    if idle_timer.tick(time.delta()).just_finished() {
+        game_state.set(GameState::Attract);
+    }
+}
+
+fn end_attract(
+    mut game_state: ResMut<NextState<GameState>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut mouse_motion: EventReader<MouseMotion>,
+) {
+    if any_input(
+        &keyboard,
+        &mouse_buttons,
+        &gamepad_buttons,
+        &mut mouse_motion,
+    ) {
+        game_state.set(GameState::Menu);
+    }
+}
+
+// Stores the current match away and clears the play area for the demo
+fn suspend_match(world: &mut World) {
+    let snapshot = MatchSnapshot::capture(world);
+
+    let mut paddle_query = world.query_filtered::<Entity, With<Paddle>>();
+    let paddles: Vec<Entity> = paddle_query.iter(world).collect();
+    let controllers = paddles
+        .into_iter()
+        .filter_map(|entity| Some((entity, world.entity_mut(entity).take::<Controller>()?)))
+        .collect();
+    world.insert_resource(SuspendedMatch {
+        snapshot,
+        controllers,
+    });
+
+    world.run_system_once(reset_ball);
+    world.run_system_once(reset_paddles);
+    world.run_system_once(reset_scores);
+}
+
+fn hand_paddles_to_cpu(mut commands: Commands, paddle_query: Query<Entity, With<Paddle>>) {
+    for entity in &paddle_query {
+        commands
+            .entity(entity)
+            .insert(Controller::new(CpuController::NORMAL));
+    }
+}
+
+// Puts the suspended match back exactly as it was left
+fn resume_match(world: &mut World) {
+    let Some(suspended) = world.remove_resource::<SuspendedMatch>() else {
+        return;
+    };
+    suspended.snapshot.apply(world);
+    for (entity, controller) in suspended.controllers {
+        world.entity_mut(entity).insert(controller);
+    }
+}
+
+fn setup_title(
+    mut commands: Commands,
+    theme: Res<ActiveTheme>,
+    settings: Res<Settings>,
+    locale: Res<Locale>,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: theme.overlay.into(),
+                ..default()
+            },
+            AttractItem,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                locale.get("menu.title"),
+                TextStyle {
+                    font: theme.font.clone(),
+                    font_size: 80. * settings.text_scale,
+                    color: theme.text,
+                },
+            ));
+            parent.spawn(
+                TextBundle::from_section(
+                    locale.get("attract.press_any_key"),
+                    TextStyle {
+                        font: theme.font.clone(),
+                        font_size: 40. * settings.text_scale,
+                        color: theme.text,
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::all(Val::Px(20.0)),
+                    ..default()
+                }),
+            );
+        });
+}
+
+fn teardown_title(mut commands: Commands, despawn_query: Query<Entity, With<AttractItem>>) {
+    for entity in &despawn_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
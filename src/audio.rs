@@ -1,62 +1,398 @@
-use bevy::prelude::*;
+use bevy::{
+    audio::{AddAudioSource, AudioSinkPlayback, Decodable, Sample, Source, Volume},
+    ecs::system::EntityCommands,
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
 
-use crate::{schedule::InGameSet, wall::GoalEvent, CollisionEvent};
+use crate::{
+    ball,
+    events::{PaddleHit, PointScored, WallBounce},
+    schedule::{GameState, InGameSet},
+    settings::Settings,
+    synth::{Synth, SynthDecoder},
+    WIDTH,
+};
 
+// Pitch rises this much for each serve speed the ball is going faster than the serve
+const PITCH_PER_SPEED: f32 = 0.2;
+// and this much more at the very edge of a paddle
+const PITCH_PER_OFFSET: f32 = 0.3;
+// Random variation either way, so a long rally doesn't grate
+const PITCH_JITTER: f32 = 0.06;
+// A hit at serve speed, getting louder up to full at twice the serve speed
+const SERVE_HIT_VOLUME: f32 = 0.6;
+// How far towards one speaker a hit at the very side of the arena is
+const MAX_PAN: f32 = 0.7;
+
+// What the ball sounds like hitting each kind of collider
 #[derive(Resource)]
-struct CollisionSound(Handle<AudioSource>);
+struct HitSounds {
+    paddle: Handle<Synth>,
+    wall: Handle<Synth>,
+}
 #[derive(Resource)]
-struct GoalSound(Handle<AudioSource>);
+struct GoalSound(Handle<Synth>);
 
 pub struct AudioPlugin;
 
 impl Plugin for AudioPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, load_audio_assets).add_systems(
-            FixedUpdate,
-            (play_collision_sound, play_goal_sound).in_set(InGameSet::EntityUpdates),
-        );
+        app.add_audio_source::<PannedSound>()
+            .add_systems(Startup, load_audio_assets)
+            .add_systems(
+                FixedUpdate,
+                (play_hit_sounds, play_goal_sound)
+                    .in_set(InGameSet::EntityUpdates)
+                    // The attract mode plays silently behind the title
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnExit(GameState::Playing), pause_gameplay_audio)
+            .add_systems(OnEnter(GameState::Playing), resume_gameplay_audio)
+            .add_systems(OnEnter(GameState::Reset), stop_gameplay_audio)
+            // After anything in Update has changed a sound's volume
+            .add_systems(PostUpdate, apply_volumes);
+    }
+}
+
+/// The mixer channel a sound plays through. Everything also goes through `Master`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bus {
+    Master,
+    Sfx,
+    Music,
+    Ui,
+}
+
+impl Bus {
+    pub const ALL: [Bus; 4] = [Bus::Master, Bus::Sfx, Bus::Music, Bus::Ui];
+
+    /// The string-table key for the label of the bus's volume setting.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Bus::Master => "audio.master",
+            Bus::Sfx => "audio.sfx",
+            Bus::Music => "audio.music",
+            Bus::Ui => "audio.ui",
+        }
+    }
+}
+
+/// One bus's fader.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BusLevel {
+    /// From 0 for silent to 1 for full.
+    pub volume: f32,
+    /// Silences the bus without losing its volume.
+    pub muted: bool,
+}
+
+impl Default for BusLevel {
+    fn default() -> Self {
+        Self {
+            volume: 1.,
+            muted: false,
+        }
+    }
+}
+
+impl BusLevel {
+    fn gain(&self) -> f32 {
+        match self.muted {
+            true => 0.,
+            false => self.volume,
+        }
+    }
+
+    /// Up in fifths, then muted, then back to the quietest.
+    pub fn next(&self) -> Self {
+        match self.muted {
+            true => Self {
+                volume: 0.2,
+                muted: false,
+            },
+            false if self.volume < 0.95 => Self {
+                volume: ((self.volume + 0.2) * 5.).round() / 5.,
+                muted: false,
+            },
+            false => Self {
+                volume: self.volume,
+                muted: true,
+            },
+        }
+    }
+}
+
+/// The level of every bus, kept in the settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Mixer {
+    pub master: BusLevel,
+    pub sfx: BusLevel,
+    pub music: BusLevel,
+    pub ui: BusLevel,
+}
+
+impl Default for Mixer {
+    fn default() -> Self {
+        Self {
+            master: BusLevel::default(),
+            sfx: BusLevel::default(),
+            music: BusLevel {
+                volume: 0.6,
+                muted: false,
+            },
+            ui: BusLevel::default(),
+        }
+    }
+}
+
+impl Mixer {
+    pub fn level(&self, bus: Bus) -> &BusLevel {
+        match bus {
+            Bus::Master => &self.master,
+            Bus::Sfx => &self.sfx,
+            Bus::Music => &self.music,
+            Bus::Ui => &self.ui,
+        }
+    }
+
+    pub fn level_mut(&mut self, bus: Bus) -> &mut BusLevel {
+        match bus {
+            Bus::Master => &mut self.master,
+            Bus::Sfx => &mut self.sfx,
+            Bus::Music => &mut self.music,
+            Bus::Ui => &mut self.ui,
+        }
+    }
+
+    /// What a sound on `bus` is multiplied by, master included.
+    pub fn gain(&self, bus: Bus) -> f32 {
+        match bus {
+            Bus::Master => self.master.gain(),
+            bus => self.master.gain() * self.level(bus).gain(),
+        }
+    }
+}
+
+/// A sound playing through the mixer, at `volume` before its bus is applied.
+#[derive(Component, Debug)]
+pub struct Sound {
+    pub bus: Bus,
+    pub volume: f32,
+}
+
+/// Plays `source` through `bus`. The volume in `settings` is the sound's own, the bus's level
+/// is applied on top and kept up to date while it plays. Every sound should be played this way.
+pub fn play_sound<'a, S: Asset + Decodable>(
+    commands: &'a mut Commands,
+    mixer: &Mixer,
+    bus: Bus,
+    source: Handle<S>,
+    settings: PlaybackSettings,
+) -> EntityCommands<'a> {
+    let volume = settings.volume.get();
+    commands.spawn((
+        AudioSourceBundle {
+            source,
+            settings: settings.with_volume(Volume::new(volume * mixer.gain(bus))),
+        },
+        Sound { bus, volume },
+    ))
+}
+
+/// Marks sounds that belong to the match, so they pause with it.
+#[derive(Component)]
+pub struct GameplayAudio;
+
+fn pause_gameplay_audio(sink_query: Query<&AudioSink, With<GameplayAudio>>) {
+    for sink in &sink_query {
+        sink.pause();
+    }
+}
+
+fn resume_gameplay_audio(sink_query: Query<&AudioSink, With<GameplayAudio>>) {
+    for sink in &sink_query {
+        sink.play();
+    }
+}
+
+// A new match shouldn't pick up where the last one's sounds left off
+fn stop_gameplay_audio(mut commands: Commands, sound_query: Query<Entity, With<GameplayAudio>>) {
+    for entity in &sound_query {
+        commands.entity(entity).despawn();
+    }
+}
+
+// Sounds start at the right volume, this keeps them there when the mixer or the sound changes
+fn apply_volumes(settings: Res<Settings>, sound_query: Query<(Ref<Sound>, &AudioSink)>) {
+    for (sound, sink) in &sound_query {
+        if settings.is_changed() || sound.is_changed() {
+            sink.set_volume(sound.volume * settings.mixer.gain(sound.bus));
+        }
+    }
+}
+
+/// A sound placed between the speakers, for things that happen somewhere in the arena.
+#[derive(Asset, TypePath, Clone)]
+pub struct PannedSound {
+    pub source: Synth,
+    /// From -1 for only the left speaker to 1 for only the right.
+    pub pan: f32,
+}
+
+impl Decodable for PannedSound {
+    type DecoderItem = f32;
+    type Decoder = Pan<SynthDecoder>;
+
+    fn decoder(&self) -> Self::Decoder {
+        // Turns down the side it is panned away from, so centered sounds are as loud as ever
+        let gains = [(1. - self.pan).min(1.), (1. + self.pan).min(1.)];
+        Pan {
+            input: self.source.decoder(),
+            gains,
+            held: None,
+            channel: 0,
+        }
+    }
+}
+
+/// Plays its input in stereo at a different volume on each side.
+pub struct Pan<S: Source>
+where
+    S::Item: Sample,
+{
+    input: S,
+    gains: [f32; 2],
+    // A mono sample, waiting to be played again on the right
+    held: Option<S::Item>,
+    channel: u16,
+}
+
+impl<S: Source> Iterator for Pan<S>
+where
+    S::Item: Sample,
+{
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<S::Item> {
+        let sample = match self.held.take() {
+            Some(sample) => sample,
+            None => {
+                let sample = self.input.next()?;
+                if self.input.channels() == 1 {
+                    self.held = Some(sample);
+                }
+                sample
+            }
+        };
+        let gain = self.gains[usize::from(self.channel % 2)];
+        self.channel = (self.channel + 1) % self.channels();
+        Some(sample.amplify(gain))
+    }
+}
+
+impl<S: Source> Source for Pan<S>
+where
+    S::Item: Sample,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        let widening = usize::from(self.channels() / self.input.channels());
+        self.input
+            .current_frame_len()
+            .map(|len| len * widening + usize::from(self.held.is_some()))
+    }
+
+    fn channels(&self) -> u16 {
+        self.input.channels().max(2)
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.input.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<std::time::Duration> {
+        self.input.total_duration()
     }
 }
 
 fn load_audio_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
-    // Bounce
-    let ball_collision_sound = asset_server.load("bounce.wav");
-    commands.insert_resource(CollisionSound(ball_collision_sound));
-    // Goal
-    let ball_goal_sound = asset_server.load("goal.wav");
-    commands.insert_resource(GoalSound(ball_goal_sound));
+    commands.insert_resource(HitSounds {
+        paddle: asset_server.load("sounds/paddle.sfx.ron"),
+        wall: asset_server.load("sounds/wall.sfx.ron"),
+    });
+    commands.insert_resource(GoalSound(asset_server.load("sounds/goal.sfx.ron")));
 }
 
-fn play_collision_sound(
+// Harder hits sound higher and louder, and come from where the ball is
+fn play_hit_sounds(
     mut commands: Commands,
-    mut collision_events: EventReader<CollisionEvent>,
-    sound: Res<CollisionSound>,
+    mut paddle_hit_events: EventReader<PaddleHit>,
+    mut wall_bounce_events: EventReader<WallBounce>,
+    sounds: Res<HitSounds>,
+    synths: Res<Assets<Synth>>,
+    mut panned_sounds: ResMut<Assets<PannedSound>>,
+    settings: Res<Settings>,
 ) {
-    // Play a sound once per frame if a collision occurred.
-    if !collision_events.is_empty() {
-        // This prevents events staying active on the next frame.
-        collision_events.clear();
-        commands.spawn(AudioBundle {
-            source: sound.0.clone(),
-            // auto-despawn the entity when playback finishes
-            settings: PlaybackSettings::DESPAWN,
+    let paddle_hits = paddle_hit_events.read().map(|hit| {
+        (
+            &sounds.paddle,
+            hit.ball_speed,
+            hit.hit_offset,
+            hit.contact.x,
+        )
+    });
+    let wall_bounces = wall_bounce_events
+        .read()
+        .map(|bounce| (&sounds.wall, bounce.ball_speed, 0., bounce.contact.x));
+
+    for (sound, ball_speed, hit_offset, x) in paddle_hits.chain(wall_bounces) {
+        // Still loading
+        let Some(synth) = synths.get(sound) else {
+            continue;
+        };
+        // In multiples of the serve speed
+        let speed = ball_speed / ball::SPEED;
+        let pitch = 1.
+            + (speed - 1.).max(0.) * PITCH_PER_SPEED
+            + hit_offset.abs() * PITCH_PER_OFFSET
+            + (fastrand::f32() * 2. - 1.) * PITCH_JITTER;
+        let volume =
+            (SERVE_HIT_VOLUME + (speed - 1.) * (1. - SERVE_HIT_VOLUME)).clamp(SERVE_HIT_VOLUME, 1.);
+        let pan = (x / (WIDTH / 2.)).clamp(-1., 1.) * MAX_PAN;
+
+        let panned = panned_sounds.add(PannedSound {
+            source: synth.clone().with_pitch(pitch),
+            pan,
         });
+        play_sound(
+            &mut commands,
+            &settings.mixer,
+            Bus::Sfx,
+            panned,
+            // auto-despawn the entity when playback finishes
+            PlaybackSettings::DESPAWN.with_volume(Volume::new(volume)),
+        )
+        .insert(GameplayAudio);
     }
 }
 
 fn play_goal_sound(
     mut commands: Commands,
-    mut goal_events: EventReader<GoalEvent>,
+    mut point_events: EventReader<PointScored>,
     sound: Res<GoalSound>,
+    settings: Res<Settings>,
 ) {
-    // Play a sound once per frame if a collision occurred.
-    if !goal_events.is_empty() {
-        // This prevents events staying active on the next frame.
-        goal_events.clear();
-        commands.spawn(AudioBundle {
-            source: sound.0.clone(),
+    for _ in point_events.read() {
+        play_sound(
+            &mut commands,
+            &settings.mixer,
+            Bus::Sfx,
+            sound.0.clone(),
             // auto-despawn the entity when playback finishes
-            settings: PlaybackSettings::DESPAWN,
-        });
+            PlaybackSettings::DESPAWN,
+        )
+        .insert(GameplayAudio);
     }
 }
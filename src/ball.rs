@@ -1,32 +1,45 @@
-use std::f32::consts::PI;
-
 use bevy::{
-    math::{
-        bounding::{Aabb2d, BoundingCircle, BoundingVolume, IntersectsVolume},
-        primitives::Circle,
-    },
+    ecs::system::RunSystemOnce,
+    math::{bounding::Aabb2d, primitives::Circle},
     prelude::*,
-    sprite::{Material2d, MaterialMesh2dBundle},
+    sprite::MaterialMesh2dBundle,
 };
 
 use crate::{
+    accessibility::{outline_mesh, OUTLINE_WIDTH},
+    collision::{self, Collision, Obstacle, Surface},
+    console::{AddConsoleCommand, ConsoleCommand, ConsoleVariable},
+    events::{PaddleHit, ServeStarted, WallBounce},
+    interpolation::Position,
+    paddle::Paddle,
     schedule::InGameSet,
+    theme::Themed,
     wall::{Goal, GoalEvent},
-    ServeDirection, ServeTimer,
+    Rally, ServeDirection, ServeTimer, Side,
 };
-use crate::{Collider, CollisionEvent, Velocity};
+use crate::{Collider, Velocity};
 
-const COLOR: Color = Color::WHITE;
-const RADIUS: f32 = 10.;
-const SPEED: f32 = 400.;
-const MAX_BOUNCE_ANGLE: f32 = 70.;
+pub const RADIUS: f32 = 10.;
+pub const SPEED: f32 = 400.;
 const START_POSITION: Vec3 = Vec3::new(0., 0., 0.);
 
 pub struct BallPlugin;
 
 impl Plugin for BallPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, spawn_ball)
+        app.init_resource::<LastContact>()
+            .add_console_command(ConsoleCommand {
+                name: "spawn",
+                usage: "spawn ball",
+                arguments: &["ball"],
+                run: spawn_command,
+            })
+            .add_console_variable(ConsoleVariable {
+                name: "ball.speed",
+                get: ball_speed,
+                set: set_ball_speed,
+            })
+            .add_systems(Startup, spawn_ball)
             .add_systems(
                 FixedUpdate,
                 (serve_ball, move_ball)
@@ -47,28 +60,76 @@ impl Plugin for BallPlugin {
 #[derive(Debug, Component)]
 pub struct Ball;
 
-#[derive(Bundle)]
-struct BallBundle<M: Material2d> {
-    ball: Ball,
-    mesh: MaterialMesh2dBundle<M>,
-}
-
 fn spawn_ball(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
     // Ball
-    commands.spawn((
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Circle::new(RADIUS)).into(),
-            material: materials.add(ColorMaterial::from(COLOR)),
-            transform: Transform::from_translation(START_POSITION),
-            ..default()
-        },
-        Ball,
-        Velocity(Vec3::ZERO),
-    ));
+    commands
+        .spawn((
+            MaterialMesh2dBundle {
+                mesh: meshes.add(Circle::new(RADIUS)).into(),
+                material: materials.add(ColorMaterial::default()),
+                transform: Transform::from_translation(START_POSITION),
+                ..default()
+            },
+            Position::new(START_POSITION),
+            Ball,
+            Velocity(Vec3::ZERO),
+            Themed::Ball,
+        ))
+        .with_children(|parent| {
+            parent.spawn(outline_mesh(
+                meshes.add(Circle::new(RADIUS + OUTLINE_WIDTH)),
+                materials.add(ColorMaterial::default()),
+            ));
+        });
+}
+
+// There is only ever one ball, so this serves it again from the center if it already exists
+fn spawn_command(world: &mut World, arguments: &[&str]) -> Result<String, String> {
+    let ["ball"] = arguments else {
+        return Err("usage: spawn ball".to_string());
+    };
+    let mut ball_query = world.query_filtered::<(&mut Position, &mut Velocity), With<Ball>>();
+    let Some((mut position, mut velocity)) = ball_query.iter_mut(world).next() else {
+        world.run_system_once(spawn_ball);
+        return Ok("Spawned a ball".to_string());
+    };
+    position.teleport(START_POSITION);
+    velocity.0 = Vec3::ZERO;
+    world.resource_mut::<ServeTimer>().timer.reset();
+    Ok("Moved the ball back to the center to serve".to_string())
+}
+
+// In units per second, rather than multiples of the serve speed
+fn ball_speed(world: &mut World) -> String {
+    let mut ball_query = world.query_filtered::<&Velocity, With<Ball>>();
+    let speed = ball_query
+        .iter(world)
+        .next()
+        .map(|velocity| velocity.length() * SPEED)
+        .unwrap_or_default();
+    format!("{speed:.0}")
+}
+
+fn set_ball_speed(world: &mut World, value: &str) -> Result<(), String> {
+    let speed: f32 = value
+        .parse()
+        .map_err(|_| format!("{value} isn't a number"))?;
+    if speed <= 0. {
+        return Err("the speed must be above 0".to_string());
+    }
+    let mut ball_query = world.query_filtered::<&mut Velocity, With<Ball>>();
+    let Some(mut velocity) = ball_query.iter_mut(world).next() else {
+        return Err("there is no ball".to_string());
+    };
+    if velocity.length() == 0. {
+        return Err("the ball hasn't been served yet".to_string());
+    }
+    velocity.0 = velocity.normalize() * speed / SPEED;
+    Ok(())
 }
 
 fn serve_ball(
@@ -76,117 +137,114 @@ fn serve_ball(
     time: Res<Time>,
     mut serve_timer: ResMut<ServeTimer>,
     mut serve_direction: ResMut<ServeDirection>,
+    mut rally: ResMut<Rally>,
+    mut serve_events: EventWriter<ServeStarted>,
 ) {
     if serve_timer.timer.tick(time.delta()).just_finished() {
-        let direction = match *serve_direction {
-            ServeDirection::Left => Vec3::NEG_X,
-            ServeDirection::Right => Vec3::X,
+        let (direction, towards) = match *serve_direction {
+            ServeDirection::Left => (Vec3::NEG_X, Side::Left),
+            ServeDirection::Right => (Vec3::X, Side::Right),
         };
         for mut ball_velocity in &mut ball_query {
             ball_velocity.0 = direction;
         }
         *serve_direction = serve_direction.opposite();
+        *rally = Rally::default();
+        serve_events.send(ServeStarted { towards });
+    } else if serve_timer.timer.finished() {
+        rally.duration += time.delta();
     }
 }
 
-fn move_ball(mut ball_query: Query<(&mut Transform, &Velocity), With<Ball>>, time: Res<Time>) {
-    for (mut transform, velocity) in ball_query.iter_mut() {
-        transform.translation += (**velocity * SPEED) * time.delta_seconds();
+fn move_ball(mut ball_query: Query<(&mut Position, &Velocity), With<Ball>>, time: Res<Time>) {
+    for (mut position, velocity) in ball_query.iter_mut() {
+        **position += (**velocity * SPEED) * time.delta_seconds();
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
-enum Collision {
-    Left,
-    Right,
-    Top,
-    Bottom,
-}
-
-fn collide_with_side(ball: BoundingCircle, wall: Aabb2d) -> Option<Collision> {
-    if !ball.intersects(&wall) {
-        return None;
-    }
-
-    let closest = wall.closest_point(ball.center());
-    let offset = ball.center() - closest;
-    let side = if offset.x.abs() > offset.y.abs() {
-        if offset.x < 0. {
-            Collision::Left
-        } else {
-            Collision::Right
-        }
-    } else if offset.y > 0. {
-        Collision::Top
-    } else {
-        Collision::Bottom
-    };
-
-    Some(side)
-}
+/// Where and on which side of a collider the ball last bounced.
+#[derive(Resource, Default)]
+pub struct LastContact(pub Option<(Vec2, Collision)>);
 
 fn handle_collisions(
-    mut ball_query: Query<(&mut Velocity, &Transform), With<Ball>>,
-    collider_query: Query<(&Transform, &Collider, Option<&Goal>)>,
-    mut collision_events: EventWriter<CollisionEvent>,
+    mut ball_query: Query<(&mut Velocity, &Position), With<Ball>>,
+    collider_query: Query<(Entity, &Position, &Collider, Option<&Goal>, Option<&Paddle>)>,
     mut goal_events: EventWriter<GoalEvent>,
+    mut paddle_hit_events: EventWriter<PaddleHit>,
+    mut wall_bounce_events: EventWriter<WallBounce>,
+    mut rally: ResMut<Rally>,
+    mut last_contact: ResMut<LastContact>,
 ) {
-    if let Ok((mut ball_velocity, ball_transform)) = ball_query.get_single_mut() {
-        for (collider_transform, collider, maybe_goal) in &collider_query {
-            let collision = collide_with_side(
-                BoundingCircle::new(ball_transform.translation.truncate(), RADIUS),
-                Aabb2d::new(
-                    collider_transform.translation.truncate(),
-                    collider.bounding_box / 2.,
-                ),
-            );
+    let Ok((mut ball_velocity, ball_position)) = ball_query.get_single_mut() else {
+        return;
+    };
+    let colliders: Vec<_> = collider_query.iter().collect();
+    let obstacles: Vec<Obstacle> = colliders
+        .iter()
+        .map(
+            |(_, position, collider, maybe_goal, maybe_paddle)| Obstacle {
+                bounds: Aabb2d::new(position.truncate(), collider.bounding_box / 2.),
+                surface: match (maybe_goal, maybe_paddle) {
+                    (Some(_), _) => Surface::Goal,
+                    (None, Some(_)) => Surface::Paddle,
+                    (None, None) => Surface::Wall,
+                },
+            },
+        )
+        .collect();
+    let resolution = collision::resolve(
+        ball_position.truncate(),
+        ball_velocity.truncate(),
+        RADIUS,
+        &obstacles,
+    );
 
-            if let Some(collision) = collision {
-                // Handle goals
-                if let Some(goal) = maybe_goal {
-                    goal_events.send(GoalEvent(goal.side));
-                    return;
-                }
-
-                // Handle collisions with walls or paddle
-                collision_events.send_default();
-                let mut reflect_y = false;
-
-                match collision {
-                    Collision::Left | Collision::Right => {
-                        let relative_y = (ball_transform.translation.y
-                            - collider_transform.translation.y)
-                            / collider.bounding_box.y;
-                        let angle = relative_y * MAX_BOUNCE_ANGLE * PI / 180.;
-                        let direction = if collision == Collision::Left {
-                            -1.0
-                        } else {
-                            1.0
-                        };
-                        ball_velocity.x = angle.cos() * direction;
-                        ball_velocity.y = angle.sin();
-                    }
-                    Collision::Top => reflect_y = ball_velocity.y < 0.0,
-                    Collision::Bottom => reflect_y = ball_velocity.y > 0.0,
-                }
-
-                if reflect_y {
-                    ball_velocity.y = -ball_velocity.y;
-                }
+    if let Some(index) = resolution.goal {
+        if let (.., Some(goal), _) = colliders[index] {
+            goal_events.send(GoalEvent(goal.side));
+        }
+        return;
+    }
+    if resolution.contacts.is_empty() {
+        return;
+    }
+
+    ball_velocity.0 = resolution.velocity.extend(0.);
+    let ball_speed = resolution.velocity.length() * SPEED;
+    for contact in resolution.contacts {
+        let (collider_entity, .., maybe_paddle) = colliders[contact.index];
+        last_contact.0 = Some((contact.point, contact.side));
+        match maybe_paddle {
+            Some(paddle) => {
+                rally.hits += 1;
+                paddle_hit_events.send(PaddleHit {
+                    paddle: collider_entity,
+                    side: paddle.side,
+                    hit_offset: contact.offset,
+                    ball_speed,
+                    contact: contact.point,
+                });
+            }
+            None => {
+                wall_bounce_events.send(WallBounce {
+                    wall: collider_entity,
+                    ball_speed,
+                    contact: contact.point,
+                });
             }
         }
     }
 }
 
 pub fn reset_ball_goal(
-    mut ball_query: Query<(&mut Transform, &mut Velocity), With<Ball>>,
+    mut ball_query: Query<(&mut Position, &mut Velocity), With<Ball>>,
     mut serve_timer: ResMut<ServeTimer>,
     mut goal_event: EventReader<GoalEvent>,
 ) {
     for _ in goal_event.read() {
         // Reset the ball
-        for (mut ball_transform, mut ball_velocity) in &mut ball_query {
-            ball_transform.translation = Vec3::ZERO;
+        for (mut ball_position, mut ball_velocity) in &mut ball_query {
+            ball_position.teleport(START_POSITION);
             ball_velocity.0 = Vec3::ZERO;
         }
 
@@ -195,12 +253,12 @@ pub fn reset_ball_goal(
 }
 
 pub fn reset_ball(
-    mut ball_query: Query<(&mut Transform, &mut Velocity), With<Ball>>,
+    mut ball_query: Query<(&mut Position, &mut Velocity), With<Ball>>,
     mut serve_timer: ResMut<ServeTimer>,
     mut serve_direction: ResMut<ServeDirection>,
 ) {
-    for (mut ball_transform, mut ball_velocity) in &mut ball_query {
-        ball_transform.translation = Vec3::ZERO;
+    for (mut ball_position, mut ball_velocity) in &mut ball_query {
+        ball_position.teleport(START_POSITION);
         ball_velocity.0 = Vec3::ZERO;
     }
 
@@ -0,0 +1,138 @@
+//! Runs headless AI-vs-AI tournaments between registered paddle controllers.
+//!
+//! ```text
+//! cargo run --release --bin tournament -- --format bracket --matches 20 --out results
+//! ```
+
+use std::{env, fs, process::ExitCode};
+
+use bevy_pong::{
+    controller::ControllerRegistry,
+    headless::ticks_per_second,
+    tournament::{run_tournament, Format, TournamentConfig},
+};
+
+const USAGE: &str = "Usage: tournament [OPTIONS] [CONTROLLER...]
+
+Plays every listed controller (default: all registered) against the others.
+
+Options:
+    --format <round-robin|bracket>  Tournament format [default: round-robin]
+    --matches <N>                   Matches per pairing [default: 10]
+    --seed <SEED>                   Seed of the first match [default: 0]
+    --points <N>                    Points needed to win a match [default: 5]
+    --max-seconds <N>               Simulated seconds before a match is called [default: 600]
+    --out <PREFIX>                  Writes PREFIX.json and PREFIX.csv [default: tournament]
+    --list                          Lists the registered controllers
+";
+
+fn main() -> ExitCode {
+    let registry = ControllerRegistry::default();
+    match parse_args(env::args().skip(1), &registry) {
+        Ok(Some((config, out))) => run(&registry, &config, &out),
+        Ok(None) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}\n\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(registry: &ControllerRegistry, config: &TournamentConfig, out: &str) -> ExitCode {
+    let report = run_tournament(registry, config);
+
+    let json = match report.to_json() {
+        Ok(json) => json,
+        Err(error) => {
+            eprintln!("Failed to serialize results: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let csv = report.to_csv();
+    for (path, contents) in [(format!("{out}.json"), json), (format!("{out}.csv"), csv)] {
+        if let Err(error) = fs::write(&path, contents) {
+            eprintln!("Failed to write {path}: {error}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    print!("{}", report.to_csv());
+    if let Some(champion) = &report.champion {
+        println!("Champion: {champion}");
+    }
+    ExitCode::SUCCESS
+}
+
+// Returns `None` when there is nothing left to do, such as after listing the controllers
+fn parse_args(
+    mut args: impl Iterator<Item = String>,
+    registry: &ControllerRegistry,
+) -> Result<Option<(TournamentConfig, String)>, String> {
+    let mut config = TournamentConfig {
+        format: Format::RoundRobin,
+        entrants: Vec::new(),
+        matches_per_pairing: 10,
+        seed: 0,
+        points_to_win: 5,
+        max_ticks: 600 * ticks_per_second(),
+    };
+    let mut out = String::from("tournament");
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+        match arg.as_str() {
+            "--format" => {
+                config.format = match value()?.as_str() {
+                    "round-robin" => Format::RoundRobin,
+                    "bracket" => Format::Bracket,
+                    other => return Err(format!("Unknown format {other}")),
+                }
+            }
+            "--matches" => config.matches_per_pairing = parse_number(&arg, value()?)?,
+            "--seed" => config.seed = parse_number(&arg, value()?)?,
+            "--points" => config.points_to_win = parse_number(&arg, value()?)?,
+            "--max-seconds" => {
+                config.max_ticks = parse_number::<u32>(&arg, value()?)?
+                    .checked_mul(ticks_per_second())
+                    .ok_or(format!("{arg} is too long"))?
+            }
+            "--out" => out = value()?,
+            "--list" => {
+                for name in registry.names() {
+                    println!("{name}");
+                }
+                return Ok(None);
+            }
+            "-h" | "--help" => {
+                print!("{USAGE}");
+                return Ok(None);
+            }
+            name if name.starts_with('-') => return Err(format!("Unknown option {name}")),
+            name if registry.get(name).is_none() => {
+                return Err(format!("No controller registered as {name}"))
+            }
+            // Standings are kept by name, so a controller can only enter once
+            name if config.entrants.iter().any(|entrant| entrant == name) => {
+                return Err(format!("{name} is listed more than once"))
+            }
+            name => config.entrants.push(name.to_string()),
+        }
+    }
+
+    if config.entrants.is_empty() {
+        config.entrants = registry.names().map(str::to_string).collect();
+    }
+    if config.entrants.len() < 2 {
+        return Err("A tournament needs at least two controllers".to_string());
+    }
+    if config.points_to_win == 0 || config.matches_per_pairing == 0 {
+        return Err("--points and --matches must be at least 1".to_string());
+    }
+    Ok(Some((config, out)))
+}
+
+fn parse_number<T: std::str::FromStr>(arg: &str, value: String) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{arg} expects a number, got {value}"))
+}
@@ -0,0 +1,147 @@
+use bevy::prelude::*;
+
+use crate::ball;
+use crate::events::{PaddleHit, PointScored};
+use crate::schedule::{GameState, InGameSet};
+use crate::settings::Settings;
+use crate::MainCamera;
+
+// Trauma is squared before shaking, so small knocks barely move the camera
+const MAX_SHAKE_OFFSET: f32 = 12.;
+const MAX_SHAKE_ANGLE: f32 = 0.03;
+// Trauma lost per second
+const TRAUMA_DECAY: f32 = 1.5;
+const GOAL_TRAUMA: f32 = 0.6;
+const HIT_TRAUMA: f32 = 0.35;
+// How hard a paddle hit must be to shake the camera, see `hit_strength`
+const HARD_HIT: f32 = 0.8;
+
+// Longest freeze, for the hardest hits
+const HIT_STOP_SECONDS: f32 = 0.06;
+
+// Fraction of the view the camera zooms in by, recovering exponentially
+const GOAL_ZOOM: f32 = 0.05;
+const HIT_ZOOM: f32 = 0.02;
+const ZOOM_RECOVERY: f32 = 10.;
+
+pub struct CameraEffectsPlugin;
+
+impl Plugin for CameraEffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraEffects>()
+            .init_resource::<DevPause>()
+            .add_systems(
+                FixedUpdate,
+                react_to_gameplay
+                    .after(InGameSet::CollisionDetection)
+                    // The attract mode stays calm behind the title
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(Update, (update_hit_stop, move_camera).chain());
+    }
+}
+
+/// Set while the dev tools have paused the game clock. A hit-stop then leaves the clock alone,
+/// rather than unpausing it from under them.
+#[derive(Resource, Default)]
+pub struct DevPause(pub bool);
+
+#[derive(Resource, Default)]
+struct CameraEffects {
+    trauma: f32,
+    zoom: f32,
+    hit_stop: Option<Timer>,
+}
+
+impl CameraEffects {
+    fn add_trauma(&mut self, trauma: f32) {
+        self.trauma = (self.trauma + trauma).min(1.);
+    }
+
+    fn punch(&mut self, zoom: f32) {
+        self.zoom = self.zoom.max(zoom);
+    }
+}
+
+// 0.5 for a center hit at serve speed, 1 off the edge of the paddle
+fn hit_strength(hit: &PaddleHit) -> f32 {
+    hit.ball_speed / ball::SPEED * (0.5 + hit.hit_offset.abs())
+}
+
+fn react_to_gameplay(
+    mut effects: ResMut<CameraEffects>,
+    settings: Res<Settings>,
+    mut paddle_hit_events: EventReader<PaddleHit>,
+    mut point_events: EventReader<PointScored>,
+) {
+    // Nothing builds up, so there is nothing to shake or zoom
+    if settings.reduce_motion {
+        paddle_hit_events.clear();
+        point_events.clear();
+        return;
+    }
+    for hit in paddle_hit_events.read() {
+        let strength = hit_strength(hit);
+        if strength >= HARD_HIT {
+            effects.add_trauma(HIT_TRAUMA * strength);
+            effects.punch(HIT_ZOOM);
+        }
+        if settings.hit_stop {
+            let seconds = HIT_STOP_SECONDS * strength.min(1.);
+            effects.hit_stop = Some(Timer::from_seconds(seconds, TimerMode::Once));
+        }
+    }
+    for _ in point_events.read() {
+        effects.add_trauma(GOAL_TRAUMA);
+        effects.punch(GOAL_ZOOM);
+    }
+}
+
+// Freezes the game clock, so everything but the camera stops for a moment
+fn update_hit_stop(
+    mut effects: ResMut<CameraEffects>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    real_time: Res<Time<Real>>,
+    dev_pause: Res<DevPause>,
+) {
+    if dev_pause.0 {
+        effects.hit_stop = None;
+        return;
+    }
+    let Some(hit_stop) = &mut effects.hit_stop else {
+        return;
+    };
+    if hit_stop.tick(real_time.delta()).finished() {
+        virtual_time.unpause();
+        effects.hit_stop = None;
+    } else {
+        virtual_time.pause();
+    }
+}
+
+fn move_camera(
+    mut effects: ResMut<CameraEffects>,
+    settings: Res<Settings>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+    real_time: Res<Time<Real>>,
+) {
+    let delta = real_time.delta_seconds();
+    effects.trauma = (effects.trauma - TRAUMA_DECAY * delta).max(0.);
+    effects.zoom *= (-ZOOM_RECOVERY * delta).exp();
+
+    let shake = effects.trauma.powi(2) * settings.screen_shake;
+    let zoom = match settings.zoom_punch {
+        true => effects.zoom,
+        false => 0.,
+    };
+    for (mut transform, mut projection) in &mut camera_query {
+        transform.translation.x = MAX_SHAKE_OFFSET * shake * random_signed();
+        transform.translation.y = MAX_SHAKE_OFFSET * shake * random_signed();
+        transform.rotation = Quat::from_rotation_z(MAX_SHAKE_ANGLE * shake * random_signed());
+        projection.scale = 1. - zoom;
+    }
+}
+
+fn random_signed() -> f32 {
+    fastrand::f32() * 2. - 1.
+}
@@ -0,0 +1,482 @@
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState, InputSystem,
+    },
+    prelude::*,
+};
+
+use crate::theme::ActiveTheme;
+
+// Kept for scrolling back through, though only the last few are shown
+const MAX_LOG_LINES: usize = 200;
+const VISIBLE_LINES: usize = 12;
+const FONT_SIZE: f32 = 18.;
+
+/// The drop-down developer console, opened with the backtick key. Plugins add their own
+/// commands and variables with `AddConsoleCommand`.
+pub struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Console>()
+            .add_console_command(ConsoleCommand {
+                name: "help",
+                usage: "help [command]",
+                arguments: &[],
+                run: help,
+            })
+            .add_console_command(ConsoleCommand {
+                name: "clear",
+                usage: "clear",
+                arguments: &[],
+                run: clear,
+            })
+            .add_console_command(ConsoleCommand {
+                name: "set",
+                usage: "set <variable> [value]",
+                arguments: &[],
+                run: set,
+            })
+            .add_console_command(ConsoleCommand {
+                name: "timescale",
+                usage: "timescale <speed>",
+                arguments: &[],
+                run: timescale,
+            })
+            .add_systems(Startup, spawn_console)
+            .add_systems(PreUpdate, read_console_input.after(InputSystem))
+            .add_systems(Update, (execute_commands, show_console).chain());
+    }
+}
+
+/// A console command. `run` is given the words typed after its name, and returns what to print.
+pub struct ConsoleCommand {
+    pub name: &'static str,
+    /// Listed by `help`, as in `"score <left> <right>"`.
+    pub usage: &'static str,
+    /// Offered by autocomplete as the first argument.
+    pub arguments: &'static [&'static str],
+    pub run: fn(&mut World, &[&str]) -> Result<String, String>,
+}
+
+/// A value that can be read and changed with the console's `set` command.
+pub struct ConsoleVariable {
+    /// Namespaced by what it belongs to, as in `"ball.speed"`.
+    pub name: &'static str,
+    pub get: fn(&mut World) -> String,
+    pub set: fn(&mut World, &str) -> Result<(), String>,
+}
+
+/// Every registered console command and variable, by name.
+#[derive(Resource, Default)]
+pub struct ConsoleCommands {
+    commands: Vec<ConsoleCommand>,
+    variables: Vec<ConsoleVariable>,
+}
+
+impl ConsoleCommands {
+    fn command(&self, name: &str) -> Option<&ConsoleCommand> {
+        self.commands.iter().find(|command| command.name == name)
+    }
+
+    fn variable(&self, name: &str) -> Option<&ConsoleVariable> {
+        self.variables.iter().find(|variable| variable.name == name)
+    }
+
+    // What the word being typed could be, given the words before it
+    fn candidates(&self, previous: &[&str]) -> Vec<&str> {
+        match previous {
+            [] => self.commands.iter().map(|command| command.name).collect(),
+            // The console's own commands take the names of other things
+            ["help"] => self.commands.iter().map(|command| command.name).collect(),
+            ["set"] => self
+                .variables
+                .iter()
+                .map(|variable| variable.name)
+                .collect(),
+            [name] => self
+                .command(name)
+                .map(|command| command.arguments.to_vec())
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Completes the last word of `input`, as far as the candidates agree.
+    /// Returns the new input and, when more than one candidate is left, the candidates.
+    pub fn complete(&self, input: &str) -> (String, Vec<&str>) {
+        let mut words: Vec<&str> = input.split_whitespace().collect();
+        // A trailing space means the next word hasn't been started
+        let partial = match input.ends_with(' ') || input.is_empty() {
+            true => "",
+            false => words.pop().unwrap_or_default(),
+        };
+        let mut matches: Vec<&str> = self
+            .candidates(&words)
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(partial))
+            .collect();
+        matches.sort_unstable();
+
+        let completed = match matches.as_slice() {
+            [] => return (input.to_string(), Vec::new()),
+            [only] => format!("{only} "),
+            [first, rest @ ..] => rest.iter().fold(first.to_string(), |prefix, candidate| {
+                let common = prefix
+                    .chars()
+                    .zip(candidate.chars())
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                prefix.chars().take(common).collect()
+            }),
+        };
+        words.push(&completed);
+        let shown = match matches.len() {
+            1 => Vec::new(),
+            _ => matches,
+        };
+        (words.join(" "), shown)
+    }
+}
+
+/// Registers console commands and variables from a plugin, whether or not the console is added.
+pub trait AddConsoleCommand {
+    fn add_console_command(&mut self, command: ConsoleCommand) -> &mut Self;
+    fn add_console_variable(&mut self, variable: ConsoleVariable) -> &mut Self;
+}
+
+impl AddConsoleCommand for App {
+    fn add_console_command(&mut self, command: ConsoleCommand) -> &mut Self {
+        let mut commands = self
+            .world
+            .get_resource_or_insert_with(ConsoleCommands::default);
+        commands
+            .commands
+            .retain(|existing| existing.name != command.name);
+        commands.commands.push(command);
+        self
+    }
+
+    fn add_console_variable(&mut self, variable: ConsoleVariable) -> &mut Self {
+        let mut commands = self
+            .world
+            .get_resource_or_insert_with(ConsoleCommands::default);
+        commands
+            .variables
+            .retain(|existing| existing.name != variable.name);
+        commands.variables.push(variable);
+        self
+    }
+}
+
+#[derive(Resource, Default)]
+struct Console {
+    open: bool,
+    input: String,
+    log: Vec<String>,
+    // Oldest first
+    history: Vec<String>,
+    // Where in the history the arrow keys have got to, if they have been used since the last command
+    browsing: Option<usize>,
+    // Entered, waiting to be run with access to the whole world
+    submitted: Vec<String>,
+}
+
+impl Console {
+    fn print(&mut self, text: impl Into<String>) {
+        self.log.extend(text.into().lines().map(String::from));
+        let excess = self.log.len().saturating_sub(MAX_LOG_LINES);
+        self.log.drain(..excess);
+    }
+}
+
+#[derive(Component)]
+struct ConsolePanel;
+
+#[derive(Component)]
+struct ConsoleText;
+
+fn spawn_console(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(0.),
+                    width: Val::Percent(100.),
+                    padding: UiRect::all(Val::Px(8.)),
+                    ..default()
+                },
+                visibility: Visibility::Hidden,
+                z_index: ZIndex::Global(i32::MAX),
+                ..default()
+            },
+            ConsolePanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((TextBundle::default(), ConsoleText));
+        });
+}
+
+fn read_console_input(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut console: ResMut<Console>,
+    commands: Res<ConsoleCommands>,
+) {
+    let was_open = console.open;
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        if event.key_code == KeyCode::Backquote {
+            console.open = !console.open;
+            continue;
+        }
+        if !console.open {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Escape => console.open = false,
+            Key::Enter => {
+                let line = console.input.trim().to_string();
+                console.input.clear();
+                console.browsing = None;
+                if !line.is_empty() {
+                    console.history.push(line.clone());
+                    console.submitted.push(line);
+                }
+            }
+            Key::Backspace => {
+                console.input.pop();
+            }
+            Key::Tab => {
+                let (completed, candidates) = commands.complete(&console.input);
+                if !candidates.is_empty() {
+                    console.print(candidates.join("  "));
+                }
+                console.input = completed;
+            }
+            Key::ArrowUp => {
+                let Some(last) = console.history.len().checked_sub(1) else {
+                    continue;
+                };
+                let index = console
+                    .browsing
+                    .map_or(last, |index| index.saturating_sub(1));
+                console.browsing = Some(index);
+                console.input = console.history[index].clone();
+            }
+            Key::ArrowDown => {
+                let Some(index) = console.browsing else {
+                    continue;
+                };
+                match console.history.get(index + 1).cloned() {
+                    Some(line) => {
+                        console.browsing = Some(index + 1);
+                        console.input = line;
+                    }
+                    None => {
+                        console.browsing = None;
+                        console.input.clear();
+                    }
+                }
+            }
+            Key::Space => console.input.push(' '),
+            Key::Character(text) => {
+                console
+                    .input
+                    .extend(text.chars().filter(|char| !char.is_control()));
+            }
+            _ => {}
+        }
+    }
+    // Typing into the console shouldn't also move paddles or open the menu
+    if was_open || console.open {
+        keys.reset_all();
+    }
+}
+
+fn execute_commands(world: &mut World) {
+    let lines = std::mem::take(&mut world.resource_mut::<Console>().submitted);
+    for line in lines {
+        world.resource_mut::<Console>().print(format!("> {line}"));
+        let output = run_line(world, &line);
+        world.resource_mut::<Console>().print(output);
+    }
+}
+
+/// Runs a line as if it had been typed into the console, and returns what it printed.
+pub fn run_line(world: &mut World, line: &str) -> String {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let Some((name, arguments)) = words.split_first() else {
+        return String::new();
+    };
+    let Some(run) = world
+        .resource::<ConsoleCommands>()
+        .command(name)
+        .map(|command| command.run)
+    else {
+        return format!("Unknown command {name}, try help");
+    };
+    match run(world, arguments) {
+        Ok(output) => output,
+        Err(error) => format!("Error: {error}"),
+    }
+}
+
+fn show_console(
+    console: Res<Console>,
+    theme: Res<ActiveTheme>,
+    mut panel_query: Query<(&mut Visibility, &mut BackgroundColor), With<ConsolePanel>>,
+    mut text_query: Query<&mut Text, With<ConsoleText>>,
+) {
+    if !console.is_changed() && !theme.is_changed() {
+        return;
+    }
+    for (mut visibility, mut background) in &mut panel_query {
+        *visibility = match console.open {
+            true => Visibility::Inherited,
+            false => Visibility::Hidden,
+        };
+        *background = theme.panel.into();
+    }
+    let start = console.log.len().saturating_sub(VISIBLE_LINES);
+    let mut lines: Vec<&str> = console.log[start..].iter().map(String::as_str).collect();
+    let prompt = format!("> {}_", console.input);
+    lines.push(&prompt);
+    for mut text in &mut text_query {
+        *text = Text::from_section(
+            lines.join("\n"),
+            TextStyle {
+                font: theme.font.clone(),
+                font_size: FONT_SIZE,
+                color: theme.text,
+            },
+        );
+    }
+}
+
+fn help(world: &mut World, arguments: &[&str]) -> Result<String, String> {
+    let commands = world.resource::<ConsoleCommands>();
+    match arguments {
+        [] => {
+            let mut usages: Vec<&str> = commands
+                .commands
+                .iter()
+                .map(|command| command.usage)
+                .collect();
+            usages.sort_unstable();
+            Ok(usages.join("\n"))
+        }
+        [name] => commands
+            .command(name)
+            .map(|command| command.usage.to_string())
+            .ok_or_else(|| format!("no command called {name}")),
+        _ => Err("usage: help [command]".to_string()),
+    }
+}
+
+fn clear(world: &mut World, _arguments: &[&str]) -> Result<String, String> {
+    world.resource_mut::<Console>().log.clear();
+    Ok(String::new())
+}
+
+fn set(world: &mut World, arguments: &[&str]) -> Result<String, String> {
+    let (name, value) = match arguments {
+        [name] => (name, None),
+        [name, value] => (name, Some(value)),
+        _ => return Err("usage: set <variable> [value]".to_string()),
+    };
+    let Some((get, set)) = world
+        .resource::<ConsoleCommands>()
+        .variable(name)
+        .map(|variable| (variable.get, variable.set))
+    else {
+        return Err(format!("no variable called {name}"));
+    };
+    if let Some(value) = value {
+        set(world, value)?;
+    }
+    Ok(format!("{name} = {}", get(world)))
+}
+
+fn timescale(world: &mut World, arguments: &[&str]) -> Result<String, String> {
+    let [speed] = arguments else {
+        return Err("usage: timescale <speed>".to_string());
+    };
+    let speed: f32 = speed
+        .parse()
+        .map_err(|_| format!("{speed} isn't a number"))?;
+    if speed <= 0. {
+        return Err("the speed must be above 0".to_string());
+    }
+    world
+        .resource_mut::<Time<Virtual>>()
+        .set_relative_speed(speed);
+    Ok(format!("Time is running at {speed}x"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_op(_world: &mut World, _arguments: &[&str]) -> Result<String, String> {
+        Ok(String::new())
+    }
+
+    fn registry() -> ConsoleCommands {
+        let mut app = App::new();
+        for (name, arguments) in [
+            ("score", &[][..]),
+            ("set", &[]),
+            ("spawn", &["ball"]),
+            ("state", &["menu", "playing", "reset"]),
+        ] {
+            app.add_console_command(ConsoleCommand {
+                name,
+                usage: name,
+                arguments,
+                run: no_op,
+            });
+        }
+        app.add_console_variable(ConsoleVariable {
+            name: "ball.speed",
+            get: |_| String::new(),
+            set: |_, _| Ok(()),
+        });
+        app.world.remove_resource::<ConsoleCommands>().unwrap()
+    }
+
+    #[test]
+    fn completes_commands_and_arguments() {
+        let commands = registry();
+        assert_eq!(commands.complete("sp"), ("spawn ".to_string(), vec![]));
+        assert_eq!(
+            commands.complete("spawn "),
+            ("spawn ball ".to_string(), vec![])
+        );
+        assert_eq!(
+            commands.complete("set b"),
+            ("set ball.speed ".to_string(), vec![])
+        );
+        // Only as far as they agree, listing what they could be
+        assert_eq!(
+            commands.complete("s"),
+            ("s".to_string(), vec!["score", "set", "spawn", "state"])
+        );
+        assert_eq!(
+            commands.complete("state "),
+            ("state ".to_string(), vec!["menu", "playing", "reset"])
+        );
+        assert_eq!(
+            commands.complete("state p"),
+            ("state playing ".to_string(), vec![])
+        );
+        assert_eq!(
+            commands.complete("nothing"),
+            ("nothing".to_string(), vec![])
+        );
+    }
+}
@@ -0,0 +1,448 @@
+use bevy::{
+    input::gamepad::{GamepadConnection, GamepadConnectionEvent},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::ball::{self, Ball};
+use crate::console::{AddConsoleCommand, ConsoleCommand};
+use crate::interpolation::Position;
+use crate::paddle::{self, Paddle, Player};
+use crate::schedule::{GameState, InGameSet};
+use crate::{Side, Velocity, HEIGHT};
+
+const CPU_DIFFERENCE_TOLERANCE: f32 = 7.;
+const GAMEPAD_DEADZONE: f32 = 0.1;
+// How far off center the predictive AI may aim, as a fraction of the paddle height
+const PREDICTIVE_MAX_AIM: f32 = 0.4;
+const PREDICTIVE_RESPONSE: f32 = 20.;
+const PREDICTIVE_SPEED: f32 = 0.7;
+const EASY_CPU_SPEED: f32 = 0.6;
+
+pub struct ControllerPlugin;
+
+impl Plugin for ControllerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ControllerRegistry>()
+            .init_resource::<Difficulty>()
+            .add_console_command(ConsoleCommand {
+                name: "ai",
+                usage: "ai <easy|normal|hard|impossible>",
+                arguments: &["easy", "normal", "hard", "impossible"],
+                run: ai_command,
+            })
+            .add_systems(OnEnter(GameState::Reset), apply_difficulty)
+            .add_systems(Update, assign_gamepads)
+            .add_systems(FixedUpdate, drive_paddles.in_set(InGameSet::Input));
+    }
+}
+
+/// Read-only view of the game handed to a `PaddleController` every fixed tick.
+pub struct GameSnapshot<'a> {
+    pub side: Side,
+    pub paddle_position: Vec2,
+    pub ball_position: Option<Vec2>,
+    pub ball_velocity: Option<Vec2>,
+    pub keyboard: &'a ButtonInput<KeyCode>,
+    pub gamepad_axes: &'a Axis<GamepadAxis>,
+    pub gamepad_buttons: &'a ButtonInput<GamepadButton>,
+}
+
+/// Anything that can drive a paddle: a human, an AI, a network peer, a replay...
+pub trait PaddleController: Send + Sync + 'static {
+    /// Identifies the controller in save files. AIs should return the name they are registered under.
+    fn name(&self) -> &str;
+
+    /// Returns the desired vertical velocity as a multiple of the paddle speed,
+    /// positive being up.
+    fn desired_velocity(&mut self, snapshot: &GameSnapshot) -> f32;
+
+    /// The gamepad this controller reads, if any.
+    fn gamepad(&self) -> Option<Gamepad> {
+        None
+    }
+}
+
+/// The controller driving a paddle. Swap it out by inserting a new one.
+#[derive(Component)]
+pub struct Controller(pub Box<dyn PaddleController>);
+
+impl Controller {
+    pub fn new(controller: impl PaddleController) -> Self {
+        Self(Box::new(controller))
+    }
+}
+
+pub struct KeyboardController {
+    pub up: KeyCode,
+    pub down: KeyCode,
+}
+
+impl KeyboardController {
+    pub fn arrows() -> Self {
+        Self {
+            up: KeyCode::ArrowUp,
+            down: KeyCode::ArrowDown,
+        }
+    }
+}
+
+impl PaddleController for KeyboardController {
+    fn name(&self) -> &str {
+        "keyboard"
+    }
+
+    fn desired_velocity(&mut self, snapshot: &GameSnapshot) -> f32 {
+        let mut vertical_direction = 0.;
+        if snapshot.keyboard.pressed(self.up) {
+            vertical_direction += 1.;
+        }
+        if snapshot.keyboard.pressed(self.down) {
+            vertical_direction -= 1.;
+        }
+        vertical_direction
+    }
+}
+
+pub struct GamepadController(pub Gamepad);
+
+impl PaddleController for GamepadController {
+    fn name(&self) -> &str {
+        "gamepad"
+    }
+
+    fn gamepad(&self) -> Option<Gamepad> {
+        Some(self.0)
+    }
+
+    fn desired_velocity(&mut self, snapshot: &GameSnapshot) -> f32 {
+        let stick = snapshot
+            .gamepad_axes
+            .get(GamepadAxis::new(self.0, GamepadAxisType::LeftStickY))
+            .unwrap_or(0.);
+        if stick.abs() > GAMEPAD_DEADZONE {
+            return stick;
+        }
+
+        let mut vertical_direction = 0.;
+        if snapshot
+            .gamepad_buttons
+            .pressed(GamepadButton::new(self.0, GamepadButtonType::DPadUp))
+        {
+            vertical_direction += 1.;
+        }
+        if snapshot
+            .gamepad_buttons
+            .pressed(GamepadButton::new(self.0, GamepadButtonType::DPadDown))
+        {
+            vertical_direction -= 1.;
+        }
+        vertical_direction
+    }
+}
+
+/// The built-in AI, which simply follows the height of the ball.
+pub struct CpuController {
+    /// Scales how fast the paddle chases the ball.
+    pub speed: f32,
+}
+
+impl CpuController {
+    pub const EASY: Self = Self {
+        speed: EASY_CPU_SPEED,
+    };
+    pub const NORMAL: Self = Self { speed: 1. };
+}
+
+impl PaddleController for CpuController {
+    fn name(&self) -> &str {
+        "cpu"
+    }
+
+    fn desired_velocity(&mut self, snapshot: &GameSnapshot) -> f32 {
+        // Fixes the paddle shooting off after the ball dissapears
+        let Some(ball_position) = snapshot.ball_position else {
+            return 0.;
+        };
+        let paddle_ball_height_difference = snapshot.paddle_position.y - ball_position.y;
+        let velocity = match paddle_ball_height_difference {
+            diff if diff > CPU_DIFFERENCE_TOLERANCE => -diff / 100.,
+            diff if diff < -CPU_DIFFERENCE_TOLERANCE => -diff / 100.,
+            _ => 0.,
+        };
+        velocity * self.speed
+    }
+}
+
+/// Looks ahead to where the ball will cross the paddle, including wall bounces,
+/// and aims a seeded random distance off center to vary the return angle.
+pub struct PredictiveController {
+    rng: fastrand::Rng,
+    aim: f32,
+    approaching: bool,
+    // Always plays off the edge of the paddle for the steepest returns, at full speed
+    ruthless: bool,
+}
+
+impl PredictiveController {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: fastrand::Rng::with_seed(seed),
+            aim: 0.,
+            approaching: false,
+            ruthless: false,
+        }
+    }
+
+    pub fn ruthless(seed: u64) -> Self {
+        Self {
+            ruthless: true,
+            ..Self::new(seed)
+        }
+    }
+}
+
+impl PaddleController for PredictiveController {
+    fn name(&self) -> &str {
+        match self.ruthless {
+            true => "impossible",
+            false => "predictive",
+        }
+    }
+
+    fn desired_velocity(&mut self, snapshot: &GameSnapshot) -> f32 {
+        let (Some(ball_position), Some(ball_velocity)) =
+            (snapshot.ball_position, snapshot.ball_velocity)
+        else {
+            return 0.;
+        };
+
+        let approaching = match snapshot.side {
+            Side::Left => ball_velocity.x < 0.,
+            Side::Right => ball_velocity.x > 0.,
+        };
+        // Pick a new aim point every time the ball turns towards us
+        if approaching && !self.approaching {
+            let aim = match self.ruthless {
+                true => self.rng.choice([-0.75, 0.75]).unwrap_or(0.75),
+                false => self.rng.f32() * 2. - 1.,
+            };
+            self.aim = aim * PREDICTIVE_MAX_AIM * paddle::SIZE.y;
+        }
+        self.approaching = approaching;
+
+        let target = if approaching {
+            predict_intercept(ball_position, ball_velocity, snapshot.paddle_position.x) - self.aim
+        } else {
+            0.
+        };
+        let max_speed = match self.ruthless {
+            true => 1.,
+            false => PREDICTIVE_SPEED,
+        };
+        ((target - snapshot.paddle_position.y) / PREDICTIVE_RESPONSE).clamp(-max_speed, max_speed)
+    }
+}
+
+/// Height at which a ball travelling along `velocity` reaches `x`, reflecting off the top and bottom walls.
+pub fn predict_intercept(position: Vec2, velocity: Vec2, x: f32) -> f32 {
+    if velocity.x == 0. {
+        return position.y;
+    }
+    let travel = (x - position.x) / velocity.x;
+    let unfolded_y = position.y + velocity.y * travel.max(0.);
+
+    // Fold the straight line back into the play area
+    let half_height = HEIGHT / 2. - ball::RADIUS;
+    let period = half_height * 4.;
+    let folded = (unfolded_y + half_height).rem_euclid(period);
+    if folded <= half_height * 2. {
+        folded - half_height
+    } else {
+        period - folded - half_height
+    }
+}
+
+/// How hard the opponent of a human player is. Applied whenever a new match starts,
+/// and kept on the CPU paddle it was applied to.
+#[derive(
+    Resource, Component, Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Impossible,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Impossible,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "difficulty.easy",
+            Difficulty::Normal => "difficulty.normal",
+            Difficulty::Hard => "difficulty.hard",
+            Difficulty::Impossible => "difficulty.impossible",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Impossible,
+            Difficulty::Impossible => Difficulty::Easy,
+        }
+    }
+
+    pub fn controller(&self) -> Controller {
+        let seed = fastrand::u64(..);
+        match self {
+            Difficulty::Easy => Controller::new(CpuController::EASY),
+            Difficulty::Normal => Controller::new(CpuController::NORMAL),
+            Difficulty::Hard => Controller::new(PredictiveController::new(seed)),
+            Difficulty::Impossible => Controller::new(PredictiveController::ruthless(seed)),
+        }
+    }
+}
+
+/// Builds a controller from a seed, so matches between AIs can be replayed.
+pub type ControllerFactory = fn(u64) -> Box<dyn PaddleController>;
+
+/// Named AIs that can be looked up by tools such as the tournament runner.
+#[derive(Resource)]
+pub struct ControllerRegistry {
+    entries: Vec<(String, ControllerFactory)>,
+}
+
+impl ControllerRegistry {
+    pub fn register(&mut self, name: impl Into<String>, factory: ControllerFactory) -> &mut Self {
+        let name = name.into();
+        self.entries.retain(|(existing, _)| *existing != name);
+        self.entries.push((name, factory));
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<ControllerFactory> {
+        self.entries
+            .iter()
+            .find(|(existing, _)| existing == name)
+            .map(|(_, factory)| *factory)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(name, _)| name.as_str())
+    }
+}
+
+impl Default for ControllerRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            entries: Vec::new(),
+        };
+        registry
+            .register("easy", |_| Box::new(CpuController::EASY))
+            .register("cpu", |_| Box::new(CpuController::NORMAL))
+            .register("predictive", |seed| {
+                Box::new(PredictiveController::new(seed))
+            })
+            .register("impossible", |seed| {
+                Box::new(PredictiveController::ruthless(seed))
+            });
+        registry
+    }
+}
+
+fn drive_paddles(
+    mut paddle_query: Query<(&Paddle, &Position, &mut Velocity, &mut Controller)>,
+    ball_query: Query<(&Position, &Velocity), (With<Ball>, Without<Paddle>)>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+) {
+    let ball = ball_query.get_single().ok();
+    for (paddle, paddle_position, mut paddle_velocity, mut controller) in &mut paddle_query {
+        let snapshot = GameSnapshot {
+            side: paddle.side,
+            paddle_position: paddle_position.truncate(),
+            ball_position: ball.map(|(position, _)| position.truncate()),
+            ball_velocity: ball.map(|(_, velocity)| velocity.truncate()),
+            keyboard: &keyboard,
+            gamepad_axes: &gamepad_axes,
+            gamepad_buttons: &gamepad_buttons,
+        };
+        paddle_velocity.y = controller.0.desired_velocity(&snapshot);
+    }
+}
+
+fn apply_difficulty(
+    mut commands: Commands,
+    cpu_query: Query<Entity, (With<Paddle>, Without<Player>)>,
+    difficulty: Res<Difficulty>,
+) {
+    for entity in &cpu_query {
+        commands
+            .entity(entity)
+            .insert((difficulty.controller(), *difficulty));
+    }
+}
+
+// Changes the opponent straight away, rather than from the next match
+fn ai_command(world: &mut World, arguments: &[&str]) -> Result<String, String> {
+    let [name] = arguments else {
+        return Err("usage: ai <easy|normal|hard|impossible>".to_string());
+    };
+    let Some(difficulty) = Difficulty::ALL
+        .into_iter()
+        .find(|difficulty| format!("{difficulty:?}").eq_ignore_ascii_case(name))
+    else {
+        return Err(format!("no difficulty called {name}"));
+    };
+    world.insert_resource(difficulty);
+    let mut cpu_query = world.query_filtered::<Entity, (With<Paddle>, Without<Player>)>();
+    let cpus: Vec<Entity> = cpu_query.iter(world).collect();
+    for entity in cpus {
+        world
+            .entity_mut(entity)
+            .insert((difficulty.controller(), difficulty));
+    }
+    Ok(format!("The AI is now {difficulty:?}"))
+}
+
+// Hands the player paddle over to a gamepad when one is plugged in, and back to the keyboard
+// when that gamepad is removed. Other gamepads are ignored while one is in use.
+fn assign_gamepads(
+    mut commands: Commands,
+    mut connection_events: EventReader<GamepadConnectionEvent>,
+    player_query: Query<(Entity, &Controller), With<Player>>,
+) {
+    let Ok((entity, controller)) = player_query.get_single() else {
+        return;
+    };
+    // The new controller isn't inserted until later, so keep track through several events
+    let mut in_use = controller.0.gamepad();
+    for event in connection_events.read() {
+        match event.connection {
+            GamepadConnection::Connected(_) if in_use.is_none() => {
+                commands
+                    .entity(entity)
+                    .insert(Controller::new(GamepadController(event.gamepad)));
+                in_use = Some(event.gamepad);
+            }
+            GamepadConnection::Disconnected if in_use == Some(event.gamepad) => {
+                commands
+                    .entity(entity)
+                    .insert(Controller::new(KeyboardController::arrows()));
+                in_use = None;
+            }
+            _ => {}
+        }
+    }
+}
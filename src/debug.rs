@@ -0,0 +1,236 @@
+use bevy::{
+    diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+};
+
+use crate::ball::{self, Ball, LastContact};
+use crate::collision::Collision;
+use crate::controller::predict_intercept;
+use crate::interpolation::Position;
+use crate::paddle::{Paddle, Player};
+use crate::theme::ActiveTheme;
+use crate::{Collider, Side, Velocity};
+
+const COLLIDER_COLOR: Color = Color::LIME_GREEN;
+const BALL_COLOR: Color = Color::YELLOW;
+const VELOCITY_COLOR: Color = Color::CYAN;
+const INTERCEPT_COLOR: Color = Color::FUCHSIA;
+const CONTACT_COLOR: Color = Color::RED;
+// The velocity arrow points to where the ball will be this long from now
+const VELOCITY_SECONDS: f32 = 0.25;
+const MARKER_RADIUS: f32 = 4.;
+// Of the line showing which way the last contact faced
+const NORMAL_LENGTH: f32 = 24.;
+const FONT_SIZE: f32 = 18.;
+
+/// Toggled with F3: shows what the collision code sees, and how the simulation is running.
+pub struct DebugOverlayPlugin;
+
+impl Plugin for DebugOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin);
+        }
+        app.init_resource::<DebugOverlay>()
+            .init_resource::<TickCounter>()
+            .add_systems(Startup, spawn_stats_text)
+            .add_systems(FixedUpdate, count_ticks)
+            .add_systems(
+                Update,
+                (
+                    (toggle_overlay, measure_tick_rate),
+                    (
+                        draw_colliders,
+                        draw_ball,
+                        draw_intercepts,
+                        draw_contact,
+                        update_stats_text,
+                    )
+                        .run_if(resource_equals(DebugOverlay(true))),
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Resource, Default, PartialEq)]
+struct DebugOverlay(bool);
+
+// Fixed ticks actually run, as opposed to the rate asked for
+#[derive(Resource, Default)]
+struct TickCounter {
+    ticks: u32,
+    elapsed: f32,
+    per_second: f32,
+}
+
+#[derive(Component)]
+struct StatsText;
+
+fn spawn_stats_text(mut commands: Commands) {
+    commands.spawn((
+        TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(8.),
+                left: Val::Px(8.),
+                padding: UiRect::axes(Val::Px(8.), Val::Px(4.)),
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            z_index: ZIndex::Global(i32::MAX),
+            ..default()
+        },
+        StatsText,
+    ));
+}
+
+fn toggle_overlay(
+    input: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<DebugOverlay>,
+    mut text_query: Query<&mut Visibility, With<StatsText>>,
+) {
+    if !input.just_pressed(KeyCode::F3) {
+        return;
+    }
+    overlay.0 = !overlay.0;
+    for mut visibility in &mut text_query {
+        *visibility = match overlay.0 {
+            true => Visibility::Inherited,
+            false => Visibility::Hidden,
+        };
+    }
+}
+
+fn count_ticks(mut counter: ResMut<TickCounter>) {
+    counter.ticks += 1;
+}
+
+fn measure_tick_rate(mut counter: ResMut<TickCounter>, real_time: Res<Time<Real>>) {
+    counter.elapsed += real_time.delta_seconds();
+    if counter.elapsed >= 1. {
+        counter.per_second = counter.ticks as f32 / counter.elapsed;
+        counter.ticks = 0;
+        counter.elapsed = 0.;
+    }
+}
+
+// Exactly the boxes `handle_collisions` tests against, at their simulated positions
+fn draw_colliders(mut gizmos: Gizmos, collider_query: Query<(&Position, &Collider)>) {
+    for (position, collider) in &collider_query {
+        gizmos.rect_2d(
+            position.truncate(),
+            0.,
+            collider.bounding_box,
+            COLLIDER_COLOR,
+        );
+    }
+}
+
+fn draw_ball(mut gizmos: Gizmos, ball_query: Query<(&Position, &Velocity), With<Ball>>) {
+    for (position, velocity) in &ball_query {
+        let center = position.truncate();
+        gizmos.circle_2d(center, ball::RADIUS, BALL_COLOR);
+        if velocity.length() > 0. {
+            let ahead = velocity.truncate() * ball::SPEED * VELOCITY_SECONDS;
+            gizmos.arrow_2d(center, center + ahead, VELOCITY_COLOR);
+        }
+    }
+}
+
+// Where each CPU paddle should meet the ball, once it is heading their way
+fn draw_intercepts(
+    mut gizmos: Gizmos,
+    ball_query: Query<(&Position, &Velocity), With<Ball>>,
+    paddle_query: Query<(&Paddle, &Position), Without<Player>>,
+) {
+    let Ok((ball_position, ball_velocity)) = ball_query.get_single() else {
+        return;
+    };
+    for (paddle, paddle_position) in &paddle_query {
+        let approaching = match paddle.side {
+            Side::Left => ball_velocity.x < 0.,
+            Side::Right => ball_velocity.x > 0.,
+        };
+        if !approaching {
+            continue;
+        }
+        let y = predict_intercept(
+            ball_position.truncate(),
+            ball_velocity.truncate(),
+            paddle_position.x,
+        );
+        let intercept = Vec2::new(paddle_position.x, y);
+        gizmos.line_2d(ball_position.truncate(), intercept, INTERCEPT_COLOR);
+        gizmos.circle_2d(intercept, MARKER_RADIUS, INTERCEPT_COLOR);
+    }
+}
+
+fn draw_contact(mut gizmos: Gizmos, last_contact: Res<LastContact>) {
+    let Some((point, side)) = last_contact.0 else {
+        return;
+    };
+    gizmos.circle_2d(point, MARKER_RADIUS, CONTACT_COLOR);
+    gizmos.line_2d(point, point + normal(side) * NORMAL_LENGTH, CONTACT_COLOR);
+}
+
+// Out of the collider, through the side that was hit
+fn normal(side: Collision) -> Vec2 {
+    match side {
+        Collision::Left => Vec2::NEG_X,
+        Collision::Right => Vec2::X,
+        Collision::Top => Vec2::Y,
+        Collision::Bottom => Vec2::NEG_Y,
+    }
+}
+
+fn update_stats_text(
+    theme: Res<ActiveTheme>,
+    diagnostics: Res<DiagnosticsStore>,
+    fixed_time: Res<Time<Fixed>>,
+    counter: Res<TickCounter>,
+    last_contact: Res<LastContact>,
+    ball_query: Query<&Velocity, With<Ball>>,
+    entity_query: Query<Entity>,
+    mut text_query: Query<(&mut Text, &mut BackgroundColor), With<StatsText>>,
+) {
+    let fps = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or_default();
+    // In multiples of the serve speed
+    let speed = ball_query
+        .get_single()
+        .map(|velocity| velocity.length())
+        .unwrap_or_default();
+    let contact = match last_contact.0 {
+        Some((point, side)) => format!("{side:?} at ({:.0}, {:.0})", point.x, point.y),
+        None => "none".to_string(),
+    };
+    let stats = [
+        format!("FPS: {fps:.0}"),
+        format!(
+            "Fixed: {:.1}/s of {:.0} Hz",
+            counter.per_second,
+            1. / fixed_time.timestep().as_secs_f32()
+        ),
+        format!(
+            "Ball: {:.0} units/s ({speed:.2}x serve)",
+            speed * ball::SPEED
+        ),
+        format!("Last contact: {contact}"),
+        format!("Entities: {}", entity_query.iter().count()),
+    ];
+
+    for (mut text, mut background) in &mut text_query {
+        *text = Text::from_section(
+            stats.join("\n"),
+            TextStyle {
+                font: theme.font.clone(),
+                font_size: FONT_SIZE,
+                color: theme.text,
+            },
+        );
+        *background = theme.toast.into();
+    }
+}
@@ -0,0 +1,115 @@
+use bevy::{
+    prelude::*,
+    render::{
+        camera::{ScalingMode, Viewport},
+        view::RenderLayers,
+    },
+    window::{PrimaryWindow, WindowMode},
+};
+
+use crate::settings::Settings;
+use crate::MainCamera;
+
+/// World units always visible, whatever the window size. Fits the arena with its walls and goals.
+pub const VIEW_WIDTH: f32 = 800.;
+pub const VIEW_HEIGHT: f32 = 450.;
+// Window height, in logical pixels, the menus were laid out for
+const UI_REFERENCE_HEIGHT: f32 = 720.;
+const LETTERBOX_COLOR: Color = Color::BLACK;
+
+pub struct DisplayPlugin;
+
+impl Plugin for DisplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_letterbox_camera)
+            .add_systems(Update, (toggle_fullscreen, fit_viewport));
+    }
+}
+
+/// The projection for the main camera, showing exactly the view area.
+pub fn projection() -> OrthographicProjection {
+    OrthographicProjection {
+        scaling_mode: ScalingMode::Fixed {
+            width: VIEW_WIDTH,
+            height: VIEW_HEIGHT,
+        },
+        ..Camera2dBundle::default().projection
+    }
+}
+
+// Clears the whole window behind the main camera's viewport, drawing the bars
+fn spawn_letterbox_camera(mut commands: Commands) {
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                order: -1,
+                clear_color: ClearColorConfig::Custom(LETTERBOX_COLOR),
+                ..default()
+            },
+            ..default()
+        },
+        // Nothing else, gizmos included, should be drawn across the whole window
+        RenderLayers::none(),
+    ));
+}
+
+fn toggle_fullscreen(
+    input: Res<ButtonInput<KeyCode>>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !input.just_pressed(KeyCode::F11) {
+        return;
+    }
+    for mut window in &mut window_query {
+        window.mode = match window.mode {
+            WindowMode::Windowed => WindowMode::BorderlessFullscreen,
+            _ => WindowMode::Windowed,
+        };
+    }
+}
+
+// Centers the largest viewport with the view's aspect ratio that fits the window
+fn fit_viewport(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<&mut Camera, With<MainCamera>>,
+    settings: Res<Settings>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let window_size = UVec2::new(window.physical_width(), window.physical_height());
+    if window_size.x == 0 || window_size.y == 0 {
+        // Minimised
+        return;
+    }
+
+    let view = Vec2::new(VIEW_WIDTH, VIEW_HEIGHT);
+    let fit = (window_size.as_vec2() / view).min_element();
+    // Whole multiples keep every world unit the same number of pixels, once the window is big enough
+    let scale = match settings.integer_scaling && fit >= 1. {
+        true => fit.floor(),
+        false => fit,
+    };
+    let size = (view * scale).round().as_uvec2().min(window_size);
+    let viewport = Viewport {
+        physical_position: (window_size - size) / 2,
+        physical_size: size,
+        ..default()
+    };
+
+    for mut camera in &mut camera_query {
+        let unchanged = camera.viewport.as_ref().is_some_and(|current| {
+            current.physical_position == viewport.physical_position
+                && current.physical_size == viewport.physical_size
+        });
+        if !unchanged {
+            camera.viewport = Some(viewport.clone());
+        }
+    }
+
+    let ui = size.y as f32 / window.scale_factor() / UI_REFERENCE_HEIGHT;
+    if ui_scale.0 != ui {
+        ui_scale.0 = ui;
+    }
+}
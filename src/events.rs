@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::Side;
+
+/// Typed gameplay events, for anything that wants to react to the match: audio, stats, effects, mods...
+pub struct EventsPlugin;
+
+impl Plugin for EventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PaddleHit>()
+            .add_event::<WallBounce>()
+            .add_event::<PointScored>()
+            .add_event::<RallyEnded>()
+            .add_event::<ServeStarted>()
+            .add_event::<MatchEnded>();
+    }
+}
+
+/// The ball bounced off a paddle.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PaddleHit {
+    pub paddle: Entity,
+    pub side: Side,
+    /// Where the ball met the paddle, relative to its height: -0.5 is the bottom edge and 0.5 the top.
+    pub hit_offset: f32,
+    /// In world units per second.
+    pub ball_speed: f32,
+    /// Where the ball touched the collider, in world space.
+    pub contact: Vec2,
+}
+
+/// The ball bounced off the top or bottom wall.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct WallBounce {
+    pub wall: Entity,
+    /// In world units per second.
+    pub ball_speed: f32,
+    /// Where the ball touched the collider, in world space.
+    pub contact: Vec2,
+}
+
+/// A side won the point.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PointScored {
+    pub scorer: Side,
+    /// The scorer's score including this point.
+    pub new_score: u32,
+}
+
+/// The rally that was just played, sent alongside `PointScored`.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct RallyEnded {
+    /// Paddle hits since the serve.
+    pub hits: u32,
+    /// Time the ball was in play.
+    pub duration: Duration,
+}
+
+/// The ball was served.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ServeStarted {
+    /// The side the ball is served towards.
+    pub towards: Side,
+}
+
+/// Sent once a side reaches the points needed to win the match.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct MatchEnded {
+    pub winner: Side,
+}
@@ -0,0 +1,166 @@
+use bevy::prelude::*;
+
+use crate::ball::Ball;
+use crate::console;
+use crate::events::{MatchEnded, PaddleHit, PointScored, RallyEnded, ServeStarted, WallBounce};
+use crate::headless::headless_app;
+use crate::interpolation::Position;
+use crate::paddle::Paddle;
+use crate::schedule::GameState;
+use crate::score::Score;
+use crate::wall::GoalEvent;
+use crate::{Side, Velocity};
+
+/// Drives a headless match for tests: scripted key presses, one fixed tick at a time,
+/// with every gameplay event sent along the way kept until it is read.
+pub struct Harness {
+    pub app: App,
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Harness {
+    /// A new match, just started and waiting to serve.
+    pub fn new() -> Self {
+        let mut app = headless_app();
+        Recorder::<GoalEvent>::add(&mut app);
+        Recorder::<PaddleHit>::add(&mut app);
+        Recorder::<WallBounce>::add(&mut app);
+        Recorder::<PointScored>::add(&mut app);
+        Recorder::<RallyEnded>::add(&mut app);
+        Recorder::<ServeStarted>::add(&mut app);
+        Recorder::<MatchEnded>::add(&mut app);
+        Self { app }
+    }
+
+    /// Advances `ticks` fixed ticks.
+    pub fn tick(&mut self, ticks: u32) {
+        for _ in 0..ticks {
+            self.app.update();
+        }
+    }
+
+    /// Ticks until `condition` holds, and returns how many ticks that took.
+    /// Panics after `max_ticks`, so a broken test fails rather than hangs.
+    pub fn tick_until(
+        &mut self,
+        max_ticks: u32,
+        mut condition: impl FnMut(&mut Self) -> bool,
+    ) -> u32 {
+        for ticks in 0..=max_ticks {
+            if condition(self) {
+                return ticks;
+            }
+            self.app.update();
+        }
+        panic!("condition not met within {max_ticks} ticks");
+    }
+
+    /// Runs a console command, and returns what it printed.
+    pub fn run_command(&mut self, line: &str) -> String {
+        console::run_line(&mut self.app.world, line)
+    }
+
+    /// Holds `key` down until it is released.
+    pub fn press(&mut self, key: KeyCode) {
+        self.app
+            .world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(key);
+    }
+
+    pub fn release(&mut self, key: KeyCode) {
+        self.app
+            .world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(key);
+    }
+
+    pub fn state(&self) -> GameState {
+        self.app.world.resource::<State<GameState>>().get().clone()
+    }
+
+    /// Takes effect on the next tick.
+    pub fn set_state(&mut self, state: GameState) {
+        self.app
+            .world
+            .resource_mut::<NextState<GameState>>()
+            .set(state);
+    }
+
+    pub fn score(&self) -> (u32, u32) {
+        let score = self.app.world.resource::<Score>();
+        (score.left, score.right)
+    }
+
+    /// Where the simulation has the ball, which the drawn transform catches up with.
+    pub fn ball_position(&mut self) -> Vec2 {
+        let mut ball_query = self.app.world.query_filtered::<&Position, With<Ball>>();
+        ball_query.single(&self.app.world).truncate()
+    }
+
+    /// Where the ball is drawn, part way between its last two positions.
+    pub fn ball_transform(&mut self) -> Vec2 {
+        let mut ball_query = self.app.world.query_filtered::<&Transform, With<Ball>>();
+        ball_query.single(&self.app.world).translation.truncate()
+    }
+
+    /// In multiples of the serve speed.
+    pub fn ball_velocity(&mut self) -> Vec2 {
+        let mut ball_query = self.app.world.query_filtered::<&Velocity, With<Ball>>();
+        ball_query.single(&self.app.world).truncate()
+    }
+
+    /// Moves the ball without it passing through anything in between.
+    pub fn place_ball(&mut self, position: Vec2, velocity: Vec2) {
+        let mut ball_query = self
+            .app
+            .world
+            .query_filtered::<(&mut Position, &mut Velocity), With<Ball>>();
+        let (mut ball_position, mut ball_velocity) = ball_query.single_mut(&mut self.app.world);
+        let z = ball_position.z;
+        ball_position.teleport(position.extend(z));
+        ball_velocity.0 = velocity.extend(0.);
+    }
+
+    pub fn paddle_position(&mut self, side: Side) -> Vec2 {
+        let mut paddle_query = self.app.world.query::<(&Position, &Paddle)>();
+        paddle_query
+            .iter(&self.app.world)
+            .find(|(_, paddle)| paddle.side == side)
+            .map(|(position, _)| position.truncate())
+            .unwrap_or_else(|| panic!("there is no {side:?} paddle"))
+    }
+
+    /// Every `E` sent since the last call.
+    /// Only the gameplay events and `GoalEvent` are kept.
+    pub fn events<E: Event + Clone>(&mut self) -> Vec<E> {
+        let recorder = self
+            .app
+            .world
+            .get_resource_mut::<Recorder<E>>()
+            .unwrap_or_else(|| panic!("{} isn't recorded", std::any::type_name::<E>()));
+        std::mem::take(&mut recorder.into_inner().events)
+    }
+}
+
+// Events only last a couple of updates, this keeps them until the test asks
+#[derive(Resource)]
+struct Recorder<E: Event> {
+    events: Vec<E>,
+}
+
+impl<E: Event + Clone> Recorder<E> {
+    fn add(app: &mut App) {
+        app.insert_resource(Self { events: Vec::new() })
+            .add_systems(Last, Self::record);
+    }
+
+    fn record(mut recorder: ResMut<Self>, mut events: EventReader<E>) {
+        recorder.events.extend(events.read().cloned());
+    }
+}
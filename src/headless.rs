@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use bevy::{input::InputPlugin, prelude::*, time::TimeUpdateStrategy};
+
+use crate::schedule::GameState;
+use crate::SimulationPlugin;
+
+/// Runs the simulation without a window, renderer or audio device.
+/// Every call to `App::update` advances exactly one fixed tick.
+pub struct HeadlessPlugin;
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            InputPlugin,
+            SimulationPlugin,
+        ))
+        // Assets the simulation entities reference, even though nothing draws them
+        .init_asset::<Mesh>()
+        .init_asset::<ColorMaterial>()
+        .init_asset::<Font>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(fixed_timestep()));
+    }
+}
+
+fn fixed_timestep() -> Duration {
+    Time::<Fixed>::default().timestep()
+}
+
+/// How many updates of a headless app make a simulated second.
+pub fn ticks_per_second() -> u32 {
+    (1. / fixed_timestep().as_secs_f64()).round() as u32
+}
+
+/// Builds a headless app and steps it until a new match is being played.
+pub fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin);
+    app.finish();
+    app.cleanup();
+
+    app.world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Reset);
+    // Startup and entering the Reset state
+    app.update();
+    // Entering the Playing state
+    app.update();
+    app
+}
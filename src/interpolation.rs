@@ -0,0 +1,55 @@
+use bevy::{prelude::*, transform::TransformSystem};
+
+pub struct InterpolationPlugin;
+
+impl Plugin for InterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedFirst, store_previous_positions)
+            .add_systems(
+                PostUpdate,
+                interpolate_transforms.before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// Where an entity is in the simulation. Fixed systems move this, and the rendered
+/// `Transform` follows it smoothly in between ticks.
+#[derive(Component, Debug, Clone, Copy, Deref, DerefMut)]
+pub struct Position {
+    #[deref]
+    current: Vec3,
+    // Where the entity was at the start of the current tick
+    previous: Vec3,
+}
+
+impl Position {
+    pub fn new(position: Vec3) -> Self {
+        Self {
+            current: position,
+            previous: position,
+        }
+    }
+
+    /// Moves without drawing the entity sliding across the screen.
+    pub fn teleport(&mut self, position: Vec3) {
+        self.current = position;
+        self.previous = position;
+    }
+}
+
+fn store_previous_positions(mut query: Query<&mut Position>) {
+    for mut position in &mut query {
+        position.previous = position.current;
+    }
+}
+
+// Draws entities part way between their last two positions, by how far the clock is into the next tick
+fn interpolate_transforms(
+    mut query: Query<(&Position, &mut Transform)>,
+    fixed_time: Res<Time<Fixed>>,
+) {
+    let fraction = fixed_time.overstep_fraction();
+    for (position, mut transform) in &mut query {
+        transform.translation = position.previous.lerp(position.current, fraction);
+    }
+}
@@ -0,0 +1,250 @@
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
+mod accessibility;
+mod achievements;
+mod attract;
+mod audio;
+mod ball;
+mod camera;
+pub mod collision;
+mod console;
+pub mod controller;
+mod debug;
+mod display;
+pub mod events;
+#[cfg(any(test, feature = "harness"))]
+pub mod harness;
+pub mod headless;
+mod interpolation;
+mod locale;
+mod menu;
+mod music;
+pub mod paddle;
+mod particles;
+mod reset;
+mod ron_asset;
+mod save;
+pub mod schedule;
+pub mod score;
+mod settings;
+mod stats;
+pub mod storage;
+mod synth;
+mod theme;
+#[cfg(feature = "dev-tools")]
+mod time_control;
+pub mod tournament;
+mod trail;
+pub mod wall;
+
+use bevy::prelude::*;
+use console::AddConsoleCommand;
+use serde::{Deserialize, Serialize};
+
+// Play Area
+pub const WIDTH: f32 = 600.;
+pub const HEIGHT: f32 = 400.;
+
+const TIME_TO_SERVE: f32 = 1.;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Side {
+    #[default]
+    Left,
+    Right,
+}
+
+#[derive(Component, Deref, DerefMut)]
+struct Velocity(Vec3);
+#[derive(Debug, Component)]
+struct Collider {
+    bounding_box: Vec2,
+}
+
+#[derive(Resource)]
+struct ServeTimer {
+    timer: Timer,
+}
+#[derive(Resource, Deref, DerefMut)]
+struct IsFirstRun(bool);
+// The player's and opponent's score in the match that just ended, shown until the next one starts
+#[derive(Resource, Default)]
+struct FinishedMatch(Option<(u32, u32)>);
+// The camera looking at the arena, as opposed to the one drawing the letterbox
+#[derive(Component)]
+struct MainCamera;
+// The rally in play since the last serve
+#[derive(Resource, Default)]
+struct Rally {
+    hits: u32,
+    duration: std::time::Duration,
+}
+
+/// Rules the current match is played under.
+#[derive(Resource, Clone, Serialize, Deserialize)]
+pub struct MatchRules {
+    pub points_to_win: u32,
+}
+
+impl Default for MatchRules {
+    fn default() -> Self {
+        Self { points_to_win: 11 }
+    }
+}
+
+impl ServeTimer {
+    fn new() -> Self {
+        Self {
+            timer: Timer::from_seconds(TIME_TO_SERVE, TimerMode::Once),
+        }
+    }
+}
+
+impl Default for ServeTimer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Default, Resource, Clone, Copy, Serialize, Deserialize)]
+enum ServeDirection {
+    Left,
+    #[default]
+    Right,
+}
+
+impl ServeDirection {
+    fn opposite(&self) -> Self {
+        match self {
+            ServeDirection::Left => ServeDirection::Right,
+            ServeDirection::Right => ServeDirection::Left,
+        }
+    }
+}
+
+/// Everything that makes up the game, on top of Bevy's `DefaultPlugins`.
+pub struct GamePlugin;
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(IsFirstRun(true))
+            .init_resource::<FinishedMatch>()
+            .add_plugins((
+                SimulationPlugin,
+                (audio::AudioPlugin, music::MusicPlugin, synth::SynthPlugin),
+                menu::MenuPlugin,
+                attract::AttractPlugin,
+                save::SavePlugin,
+                stats::StatsPlugin,
+                achievements::AchievementsPlugin,
+                settings::SettingsPlugin,
+                particles::ParticlesPlugin,
+                trail::TrailPlugin,
+                camera::CameraEffectsPlugin,
+                (
+                    display::DisplayPlugin,
+                    debug::DebugOverlayPlugin,
+                    console::ConsolePlugin,
+                ),
+                theme::ThemePlugin,
+                accessibility::AccessibilityPlugin,
+                locale::LocalePlugin,
+            ))
+            .add_systems(Startup, setup_camera)
+            .add_systems(OnEnter(schedule::GameState::Playing), update_first_play)
+            .add_systems(
+                FixedUpdate,
+                open_menu_input
+                    .in_set(schedule::InGameSet::Input)
+                    .run_if(in_state(schedule::GameState::Playing)),
+            )
+            .add_systems(
+                FixedUpdate,
+                end_match.after(schedule::InGameSet::EntityUpdates).run_if(
+                    in_state(schedule::GameState::Playing)
+                        .and_then(on_event::<events::MatchEnded>()),
+                ),
+            );
+        #[cfg(feature = "dev-tools")]
+        app.add_plugins(time_control::TimeControlPlugin);
+    }
+}
+
+/// The game rules and entities without any presentation, so they can also run headless.
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ServeDirection>()
+            // Resources
+            .init_resource::<ServeTimer>()
+            .init_resource::<MatchRules>()
+            .init_resource::<Rally>()
+            .add_console_variable(console::ConsoleVariable {
+                name: "rules.points_to_win",
+                get: |world| world.resource::<MatchRules>().points_to_win.to_string(),
+                set: |world, value| {
+                    let points = value
+                        .parse()
+                        .map_err(|_| format!("{value} isn't a number of points"))?;
+                    world.resource_mut::<MatchRules>().points_to_win = points;
+                    Ok(())
+                },
+            })
+            // User Systems
+            .add_plugins((
+                events::EventsPlugin,
+                interpolation::InterpolationPlugin,
+                ball::BallPlugin,
+                wall::WallPlugin,
+                paddle::PaddlePlugin,
+                controller::ControllerPlugin,
+                schedule::SchedulePlugin,
+                score::ScorePlugin,
+                reset::ResetBundle,
+            ));
+    }
+}
+
+fn setup_camera(mut commands: Commands) {
+    commands.spawn((
+        Camera2dBundle {
+            projection: display::projection(),
+            ..default()
+        },
+        MainCamera,
+    ));
+}
+
+fn open_menu_input(
+    input: Res<ButtonInput<KeyCode>>,
+    mut game_state: ResMut<NextState<schedule::GameState>>,
+) {
+    if input.pressed(KeyCode::Escape) {
+        game_state.set(schedule::GameState::Menu)
+    }
+}
+
+fn update_first_play(
+    mut is_first_run: ResMut<IsFirstRun>,
+    mut finished_match: ResMut<FinishedMatch>,
+) {
+    **is_first_run = false;
+    finished_match.0 = None;
+}
+
+fn end_match(
+    mut is_first_run: ResMut<IsFirstRun>,
+    mut finished_match: ResMut<FinishedMatch>,
+    scoreboard: Res<score::Score>,
+    player_query: Query<&paddle::Paddle, With<paddle::Player>>,
+    mut game_state: ResMut<NextState<schedule::GameState>>,
+) {
+    // A finished match can't be resumed
+    **is_first_run = true;
+    finished_match.0 = Some(match player_query.get_single().map(|paddle| paddle.side) {
+        Ok(Side::Left) => (scoreboard.left, scoreboard.right),
+        _ => (scoreboard.right, scoreboard.left),
+    });
+    game_state.set(schedule::GameState::Menu);
+}
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::ron_asset::RonAssetLoader;
+use crate::settings::Settings;
+
+pub struct LocalePlugin;
+
+impl Plugin for LocalePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<StringTable>()
+            .register_asset_loader(RonAssetLoader::<StringTable>::new(&["locale.ron"]))
+            .init_resource::<SelectedLocale>()
+            .init_resource::<Locale>()
+            .add_systems(Update, activate_locale.in_set(LocaleUpdate));
+    }
+}
+
+/// Where `Locale` switches to a newly selected language, for anything drawn from it to run after.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LocaleUpdate;
+
+/// Every user-facing string in one language, by key. Loaded from `assets/locales/*.locale.ron`.
+#[derive(Asset, TypePath, Debug, Clone, Default, Deserialize)]
+#[serde(transparent)]
+pub struct StringTable(HashMap<String, String>);
+
+impl StringTable {
+    // Compiled in, as the fallback for keys other languages are missing
+    fn english() -> Self {
+        ron::from_str(include_str!("../assets/locales/en.locale.ron"))
+            .expect("the English string table should be valid")
+    }
+}
+
+/// The languages that ship with the game.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+    French,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 4] = [
+        Language::English,
+        Language::Spanish,
+        Language::French,
+        Language::German,
+    ];
+
+    fn path(&self) -> &'static str {
+        match self {
+            Language::English => "locales/en.locale.ron",
+            Language::Spanish => "locales/es.locale.ron",
+            Language::French => "locales/fr.locale.ron",
+            Language::German => "locales/de.locale.ron",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|language| language == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// Looks up text in the selected language, falling back to English.
+#[derive(Resource)]
+pub struct Locale {
+    strings: StringTable,
+    english: StringTable,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self {
+            strings: StringTable::default(),
+            english: StringTable::english(),
+        }
+    }
+}
+
+impl Locale {
+    /// The key itself when no language has it, so missing text is easy to spot.
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.strings
+            .0
+            .get(key)
+            .or_else(|| self.english.0.get(key))
+            .map_or(key, String::as_str)
+    }
+
+    /// Fills in `{name}` placeholders in the text for `key`.
+    pub fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        let mut text = self.get(key).to_string();
+        for (name, value) in args {
+            text = text.replace(&format!("{{{name}}}"), &value.to_string());
+        }
+        text
+    }
+}
+
+// The string table picked in the settings, which may still be loading
+#[derive(Resource, Default)]
+struct SelectedLocale(Handle<StringTable>);
+
+// Switches to the selected language once it has loaded, and again whenever its file is edited
+fn activate_locale(
+    settings: Res<Settings>,
+    asset_server: Res<AssetServer>,
+    tables: Res<Assets<StringTable>>,
+    mut asset_events: EventReader<AssetEvent<StringTable>>,
+    mut selected: ResMut<SelectedLocale>,
+    mut locale: ResMut<Locale>,
+) {
+    let mut changed = false;
+    if settings.is_changed() {
+        let handle = asset_server.load(settings.language.path());
+        if handle != selected.0 {
+            selected.0 = handle;
+            changed = true;
+        }
+    }
+    for event in asset_events.read() {
+        changed |= event.is_loaded_with_dependencies(&selected.0) || event.is_modified(&selected.0);
+    }
+    if !changed {
+        return;
+    }
+    // Not loaded yet, its loaded event comes later
+    let Some(table) = tables.get(&selected.0) else {
+        return;
+    };
+    locale.strings = table.clone();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    fn load(language: Language) -> StringTable {
+        let path = format!("{}/assets/{}", env!("CARGO_MANIFEST_DIR"), language.path());
+        let text = std::fs::read_to_string(&path).unwrap_or_else(|error| panic!("{path}: {error}"));
+        ron::from_str(&text).unwrap_or_else(|error| panic!("{path}: {error}"))
+    }
+
+    fn placeholders(text: &str) -> BTreeSet<&str> {
+        text.split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}'))
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    #[test]
+    fn every_key_exists_in_each_locale() {
+        let english = load(Language::English);
+        for language in Language::ALL {
+            let table = load(language);
+            for (key, text) in &english.0 {
+                let Some(translation) = table.0.get(key) else {
+                    panic!("{language:?} is missing {key}");
+                };
+                assert_eq!(
+                    placeholders(translation),
+                    placeholders(text),
+                    "{language:?} has different placeholders for {key}"
+                );
+            }
+            for key in table.0.keys() {
+                assert!(
+                    english.0.contains_key(key),
+                    "{language:?} has unknown key {key}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn falls_back_to_english() {
+        let locale = Locale {
+            strings: StringTable(HashMap::from([("menu.exit".into(), "Salir".into())])),
+            english: StringTable::english(),
+        };
+        assert_eq!(locale.get("menu.exit"), "Salir");
+        assert_eq!(locale.get("menu.new_game"), "New Game");
+        assert_eq!(locale.get("no.such.key"), "no.such.key");
+        assert_eq!(
+            locale.format("achievements.count", &[("unlocked", &1), ("total", &4)]),
+            "Unlocked 1 of 4"
+        );
+    }
+}
@@ -1,54 +1,249 @@
 use bevy::{app::AppExit, prelude::*};
 
-use crate::{schedule::GameState, IsFirstRun};
+use crate::{
+    achievements::{Achievement, UnlockedAchievements},
+    audio::{play_sound, Bus},
+    controller::Difficulty,
+    locale::{Locale, LocaleUpdate},
+    save::{load_saved_match, save_exists},
+    schedule::GameState,
+    settings::Settings,
+    stats::{format_date, PlayerStats},
+    synth::Synth,
+    theme::ActiveTheme,
+    FinishedMatch, IsFirstRun,
+};
 
 pub struct MenuPlugin;
 
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::Menu), setup_menu)
+        app.init_resource::<MenuPage>()
+            .add_systems(Startup, load_menu_sounds)
+            .add_systems(OnEnter(GameState::Menu), open_main_page)
+            .add_systems(
+                Update,
+                (
+                    show_menu_page
+                        // A new language loads in the background, the page is redrawn once it is ready
+                        .after(LocaleUpdate)
+                        .run_if(
+                            in_state(GameState::Menu).and_then(
+                                resource_changed::<MenuPage>
+                                    .or_else(resource_changed::<ActiveTheme>)
+                                    .or_else(resource_changed::<Locale>),
+                            ),
+                        ),
+                    play_button_sounds.run_if(in_state(GameState::Menu)),
+                ),
+            )
             .add_systems(FixedUpdate, menu_action.run_if(in_state(GameState::Menu)))
             .add_systems(OnExit(GameState::Menu), teardown_menu);
     }
 }
 
-const TEXT_COLOR: Color = Color::WHITE;
-const BACKGROUND_COLOR: Color = Color::BLACK;
-const BUTTON_COLOR: Color = Color::DARK_GRAY;
-const DISABLED_BUTTON_COLOR: Color = Color::rgba(0.15, 0.15, 0.15, 0.8);
-const DISABLED_TEXT_COLOR: Color = Color::DARK_GRAY;
-const BORDER_COLOR: Color = Color::WHITE;
+const RECENT_MATCHES: usize = 5;
 
 #[derive(Component)]
 struct MenuItem;
 
+// Buttons for actions that can't be taken right now
 #[derive(Component)]
+struct Disabled;
+
+#[derive(Resource)]
+struct MenuSounds {
+    hover: Handle<Synth>,
+    click: Handle<Synth>,
+    back: Handle<Synth>,
+}
+
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq)]
+enum MenuPage {
+    #[default]
+    Main,
+    Stats,
+    Achievements,
+    Settings,
+    Audio,
+    Effects,
+    Accessibility,
+}
+
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
 enum MenuButtonAction {
     Resume,
+    Continue,
     New,
+    Difficulty,
+    Stats,
+    Achievements,
+    Settings,
+    // From one of the settings' own pages
+    BackToSettings,
+    ToggleParticles,
+    ToggleTrail,
+    CycleScreenShake,
+    ToggleHitStop,
+    ToggleZoomPunch,
+    ToggleIntegerScaling,
+    CycleLanguage,
+    Audio,
+    CycleVolume(Bus),
+    Effects,
+    Accessibility,
+    CycleTheme,
+    ToggleColorblind,
+    ToggleOutlines,
+    CycleTextScale,
+    ToggleReduceMotion,
+    ToggleCaptions,
+    Back,
     Quit,
 }
 
-fn setup_menu(
+// Shared look and language of every page
+struct MenuStyle<'a> {
+    theme: &'a ActiveTheme,
+    locale: &'a Locale,
+    text_scale: f32,
+    button: Style,
+    button_text: TextStyle,
+    line_text: TextStyle,
+}
+
+impl<'a> MenuStyle<'a> {
+    fn new(theme: &'a ActiveTheme, locale: &'a Locale, text_scale: f32) -> Self {
+        Self {
+            // Grows to fit larger text
+            button: Style {
+                min_width: Val::Px(300.0),
+                min_height: Val::Px(50.0),
+                padding: UiRect::horizontal(Val::Px(16.0)),
+                margin: UiRect::all(Val::Px(8.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            button_text: TextStyle {
+                font: theme.font.clone(),
+                font_size: 40.0 * text_scale,
+                color: theme.text,
+            },
+            line_text: TextStyle {
+                font: theme.font.clone(),
+                font_size: 24.0 * text_scale,
+                color: theme.text,
+            },
+            theme,
+            locale,
+            text_scale,
+        }
+    }
+
+    fn title(&self, parent: &mut ChildBuilder, key: &str, font_size: f32) {
+        parent.spawn(
+            TextBundle::from_section(
+                self.locale.get(key),
+                TextStyle {
+                    font: self.theme.font.clone(),
+                    font_size: font_size * self.text_scale,
+                    color: self.theme.text,
+                },
+            )
+            .with_style(Style {
+                margin: UiRect::all(Val::Px(30.0)),
+                ..default()
+            }),
+        );
+    }
+
+    fn line(&self, parent: &mut ChildBuilder, text: impl Into<String>) {
+        parent.spawn(
+            TextBundle::from_section(text, self.line_text.clone()).with_style(Style {
+                margin: UiRect::horizontal(Val::Px(30.0)),
+                ..default()
+            }),
+        );
+    }
+
+    fn button(
+        &self,
+        parent: &mut ChildBuilder,
+        action: MenuButtonAction,
+        label: &str,
+        enabled: bool,
+    ) {
+        let (button_color, text_color) = match enabled {
+            true => (self.theme.button, self.theme.text),
+            false => (self.theme.disabled_button, self.theme.disabled_text),
+        };
+        let mut button = parent.spawn((
+            ButtonBundle {
+                style: self.button.clone(),
+                background_color: button_color.into(),
+                ..default()
+            },
+            action,
+        ));
+        if !enabled {
+            button.insert(Disabled);
+        }
+        button.with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    color: text_color,
+                    ..self.button_text.clone()
+                },
+            ));
+        });
+    }
+
+    // A button labelled with a setting and its current value
+    fn setting(
+        &self,
+        parent: &mut ChildBuilder,
+        action: MenuButtonAction,
+        key: &str,
+        value: &str,
+        enabled: bool,
+    ) {
+        let label = self.locale.format(key, &[("value", &value)]);
+        self.button(parent, action, &label, enabled);
+    }
+
+    fn on_off(&self, enabled: bool) -> &str {
+        match enabled {
+            true => self.locale.get("common.on"),
+            false => self.locale.get("common.off"),
+        }
+    }
+}
+
+fn open_main_page(mut page: ResMut<MenuPage>) {
+    // Assigning marks the page as changed, so it is rebuilt even if it was already Main
+    *page = MenuPage::Main;
+}
+
+fn show_menu_page(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    theme: Res<ActiveTheme>,
+    locale: Res<Locale>,
+    page: Res<MenuPage>,
     is_first_run: Res<IsFirstRun>,
+    finished_match: Res<FinishedMatch>,
+    difficulty: Res<Difficulty>,
+    stats: Res<PlayerStats>,
+    achievements: Res<UnlockedAchievements>,
+    settings: Res<Settings>,
+    despawn_query: Query<Entity, With<MenuItem>>,
 ) {
-    let font = asset_server.load("fonts/PixelifySans-VariableFont_wght.ttf");
-
-    let button_style = Style {
-        width: Val::Px(250.0),
-        height: Val::Px(65.0),
-        margin: UiRect::all(Val::Px(20.0)),
-        justify_content: JustifyContent::Center,
-        align_items: AlignItems::Center,
-        ..default()
-    };
-    let button_text_style = TextStyle {
-        font: font.clone(),
-        font_size: 40.0,
-        color: TEXT_COLOR,
-    };
+    for entity in &despawn_query {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let style = MenuStyle::new(&theme, &locale, settings.text_scale);
     commands
         .spawn((
             NodeBundle {
@@ -73,89 +268,408 @@ fn setup_menu(
                         flex_direction: FlexDirection::Column,
                         align_items: AlignItems::Center,
                         border: UiRect::all(Val::Px(10.)),
+                        padding: UiRect::bottom(Val::Px(20.)),
                         ..default()
                     },
-                    background_color: BACKGROUND_COLOR.into(),
-                    border_color: BORDER_COLOR.into(),
+                    background_color: theme.panel.into(),
+                    border_color: theme.border.into(),
                     ..default()
                 })
-                .with_children(|parent| {
-                    // Game Title
-                    parent.spawn(
-                        TextBundle::from_section(
-                            "Bevy Pong",
-                            TextStyle {
-                                font,
-                                font_size: 80.,
-                                color: TEXT_COLOR,
-                            },
-                        )
-                        .with_style(Style {
-                            margin: UiRect::all(Val::Px(50.0)),
-                            ..default()
-                        }),
-                    );
-                    let (button_color, text_color) = match **is_first_run {
-                        true => (DISABLED_BUTTON_COLOR, DISABLED_TEXT_COLOR),
-                        false => (BUTTON_COLOR, TEXT_COLOR),
-                    };
-                    // Resume Game Button
-                    parent
-                        .spawn((
-                            ButtonBundle {
-                                style: button_style.clone(),
-                                background_color: button_color.into(),
-                                ..default()
-                            },
-                            MenuButtonAction::Resume,
-                        ))
-                        .with_children(|parent| {
-                            parent.spawn(TextBundle::from_section(
-                                "Resume",
-                                TextStyle {
-                                    color: text_color,
-                                    ..button_text_style.clone()
-                                },
-                            ));
-                        });
-                    // New Game Button
-                    parent
-                        .spawn((
-                            ButtonBundle {
-                                style: button_style.clone(),
-                                background_color: BUTTON_COLOR.into(),
-                                ..default()
-                            },
-                            MenuButtonAction::New,
-                        ))
-                        .with_children(|parent| {
-                            parent.spawn(TextBundle::from_section(
-                                "New Game",
-                                button_text_style.clone(),
-                            ));
-                        });
-                    // Exit Button
-                    parent
-                        .spawn((
-                            ButtonBundle {
-                                style: button_style.clone(),
-                                background_color: BUTTON_COLOR.into(),
-                                ..default()
-                            },
-                            MenuButtonAction::Quit,
-                        ))
-                        .with_children(|parent| {
-                            parent
-                                .spawn(TextBundle::from_section("Exit", button_text_style.clone()));
-                        });
+                .with_children(|parent| match *page {
+                    MenuPage::Main => {
+                        main_page(parent, &style, &is_first_run, &finished_match, *difficulty)
+                    }
+                    MenuPage::Stats => stats_page(parent, &style, &stats),
+                    MenuPage::Achievements => achievements_page(parent, &style, &achievements),
+                    MenuPage::Settings => settings_page(parent, &style, &settings),
+                    MenuPage::Audio => audio_page(parent, &style, &settings),
+                    MenuPage::Effects => effects_page(parent, &style, &settings),
+                    MenuPage::Accessibility => accessibility_page(parent, &style, &settings),
                 });
         });
 }
 
+fn main_page(
+    parent: &mut ChildBuilder,
+    style: &MenuStyle,
+    is_first_run: &IsFirstRun,
+    finished_match: &FinishedMatch,
+    difficulty: Difficulty,
+) {
+    let locale = style.locale;
+    // Game Title
+    style.title(parent, "menu.title", 80.);
+    if let Some((player, opponent)) = finished_match.0 {
+        let key = match player > opponent {
+            true => "menu.won",
+            false => "menu.lost",
+        };
+        style.line(
+            parent,
+            locale.format(key, &[("player", &player), ("opponent", &opponent)]),
+        );
+        style.line(parent, "");
+    }
+    // A match from this session takes priority over the one saved on disk
+    let (action, label) = match **is_first_run {
+        true => (MenuButtonAction::Continue, "menu.continue"),
+        false => (MenuButtonAction::Resume, "menu.resume"),
+    };
+    let enabled = !**is_first_run || save_exists();
+    style.button(parent, action, locale.get(label), enabled);
+    style.button(
+        parent,
+        MenuButtonAction::New,
+        locale.get("menu.new_game"),
+        true,
+    );
+    style.button(
+        parent,
+        MenuButtonAction::Difficulty,
+        &locale.format("menu.cpu", &[("difficulty", &locale.get(difficulty.key()))]),
+        true,
+    );
+    style.button(
+        parent,
+        MenuButtonAction::Stats,
+        locale.get("menu.stats"),
+        true,
+    );
+    style.button(
+        parent,
+        MenuButtonAction::Achievements,
+        locale.get("menu.achievements"),
+        true,
+    );
+    style.button(
+        parent,
+        MenuButtonAction::Settings,
+        locale.get("menu.settings"),
+        true,
+    );
+    style.button(
+        parent,
+        MenuButtonAction::Quit,
+        locale.get("menu.exit"),
+        true,
+    );
+}
+
+fn stats_page(parent: &mut ChildBuilder, style: &MenuStyle, stats: &PlayerStats) {
+    let locale = style.locale;
+    style.title(parent, "stats.title", 60.);
+
+    let played = stats.matches.len();
+    style.line(
+        parent,
+        locale.format(
+            "stats.summary",
+            &[
+                ("played", &played),
+                ("won", &stats.wins()),
+                ("percent", &percentage(stats.wins(), played)),
+            ],
+        ),
+    );
+    style.line(parent, "");
+    for difficulty in Difficulty::ALL {
+        let (won, played) = stats.record_against(difficulty);
+        style.line(
+            parent,
+            locale.format(
+                "stats.record",
+                &[
+                    (
+                        "difficulty",
+                        &format!("{:<10}", locale.get(difficulty.key())),
+                    ),
+                    ("won", &won),
+                    ("played", &played),
+                    ("percent", &percentage(won, played)),
+                ],
+            ),
+        );
+    }
+
+    style.line(parent, "");
+    style.line(parent, locale.get("stats.recent"));
+    if played == 0 {
+        style.line(parent, locale.get("stats.none"));
+    }
+    for record in stats.recent(RECENT_MATCHES) {
+        let duration = record.duration_secs as u32;
+        let result = match record.won() {
+            true => "stats.won",
+            false => "stats.lost",
+        };
+        style.line(
+            parent,
+            locale.format(
+                "stats.match",
+                &[
+                    ("date", &format_date(record.date)),
+                    ("mode", &locale.get(record.mode.key())),
+                    ("difficulty", &locale.get(record.difficulty.key())),
+                    (
+                        "score",
+                        &format!("{}-{}", record.player_score, record.opponent_score),
+                    ),
+                    ("result", &locale.get(result)),
+                    (
+                        "duration",
+                        &format!("{}:{:02}", duration / 60, duration % 60),
+                    ),
+                    ("rally", &record.longest_rally),
+                ],
+            ),
+        );
+    }
+
+    style.button(
+        parent,
+        MenuButtonAction::Back,
+        locale.get("common.back"),
+        true,
+    );
+}
+
+fn achievements_page(
+    parent: &mut ChildBuilder,
+    style: &MenuStyle,
+    achievements: &UnlockedAchievements,
+) {
+    let locale = style.locale;
+    style.title(parent, "achievements.title", 60.);
+
+    for achievement in Achievement::ALL {
+        let name = locale.get(achievement.name_key());
+        let description = locale.get(achievement.description_key());
+        let line = match achievements.0.get(&achievement) {
+            Some(&date) => locale.format(
+                "achievements.unlocked",
+                &[
+                    ("name", &name),
+                    ("description", &description),
+                    ("date", &format_date(date)),
+                ],
+            ),
+            None => locale.format(
+                "achievements.locked",
+                &[("name", &name), ("description", &description)],
+            ),
+        };
+        style.line(parent, line);
+    }
+    style.line(parent, "");
+    style.line(
+        parent,
+        locale.format(
+            "achievements.count",
+            &[
+                ("unlocked", &achievements.0.len()),
+                ("total", &Achievement::ALL.len()),
+            ],
+        ),
+    );
+
+    style.button(
+        parent,
+        MenuButtonAction::Back,
+        locale.get("common.back"),
+        true,
+    );
+}
+
+fn settings_page(parent: &mut ChildBuilder, style: &MenuStyle, settings: &Settings) {
+    let locale = style.locale;
+    style.title(parent, "settings.title", 60.);
+    // Every language's name is shown in that language
+    style.button(
+        parent,
+        MenuButtonAction::CycleLanguage,
+        &locale.format(
+            "settings.language",
+            &[("language", &locale.get("language.name"))],
+        ),
+        true,
+    );
+    style.setting(
+        parent,
+        MenuButtonAction::ToggleIntegerScaling,
+        "settings.pixel_perfect",
+        style.on_off(settings.integer_scaling),
+        true,
+    );
+    style.button(
+        parent,
+        MenuButtonAction::Audio,
+        locale.get("settings.audio"),
+        true,
+    );
+    style.button(
+        parent,
+        MenuButtonAction::Effects,
+        locale.get("settings.effects"),
+        true,
+    );
+    style.button(
+        parent,
+        MenuButtonAction::Accessibility,
+        locale.get("settings.accessibility"),
+        true,
+    );
+    style.button(
+        parent,
+        MenuButtonAction::Back,
+        locale.get("common.back"),
+        true,
+    );
+}
+
+fn audio_page(parent: &mut ChildBuilder, style: &MenuStyle, settings: &Settings) {
+    style.title(parent, "audio.title", 60.);
+    for bus in Bus::ALL {
+        let level = settings.mixer.level(bus);
+        let value = match level.muted {
+            true => style.locale.get("common.off").to_string(),
+            false => format!("{}%", (level.volume * 100.).round()),
+        };
+        style.setting(
+            parent,
+            MenuButtonAction::CycleVolume(bus),
+            bus.key(),
+            &value,
+            true,
+        );
+    }
+    // Back to the settings, rather than the main page
+    style.button(
+        parent,
+        MenuButtonAction::BackToSettings,
+        style.locale.get("common.back"),
+        true,
+    );
+}
+
+fn effects_page(parent: &mut ChildBuilder, style: &MenuStyle, settings: &Settings) {
+    style.title(parent, "effects.title", 60.);
+    style.setting(
+        parent,
+        MenuButtonAction::ToggleParticles,
+        "effects.particles",
+        style.on_off(settings.particles),
+        true,
+    );
+    style.setting(
+        parent,
+        MenuButtonAction::ToggleTrail,
+        "effects.trail",
+        style.on_off(settings.trail),
+        true,
+    );
+    let shake = match settings.screen_shake {
+        shake if shake <= 0. => style.on_off(false).to_string(),
+        shake => format!("{}%", (shake * 100.).round()),
+    };
+    style.setting(
+        parent,
+        MenuButtonAction::CycleScreenShake,
+        "effects.shake",
+        &shake,
+        true,
+    );
+    style.setting(
+        parent,
+        MenuButtonAction::ToggleHitStop,
+        "effects.hit_stop",
+        style.on_off(settings.hit_stop),
+        true,
+    );
+    style.setting(
+        parent,
+        MenuButtonAction::ToggleZoomPunch,
+        "effects.zoom",
+        style.on_off(settings.zoom_punch),
+        true,
+    );
+    // Back to the settings, rather than the main page
+    style.button(
+        parent,
+        MenuButtonAction::BackToSettings,
+        style.locale.get("common.back"),
+        true,
+    );
+}
+
+fn accessibility_page(parent: &mut ChildBuilder, style: &MenuStyle, settings: &Settings) {
+    style.title(parent, "accessibility.title", 60.);
+    // The colorblind palette takes the place of the theme
+    style.setting(
+        parent,
+        MenuButtonAction::CycleTheme,
+        "accessibility.theme",
+        style.locale.get(settings.theme.key()),
+        !settings.colorblind,
+    );
+    style.setting(
+        parent,
+        MenuButtonAction::ToggleColorblind,
+        "accessibility.colorblind",
+        style.on_off(settings.colorblind),
+        true,
+    );
+    style.setting(
+        parent,
+        MenuButtonAction::ToggleOutlines,
+        "accessibility.outlines",
+        style.on_off(settings.outlines),
+        true,
+    );
+    style.setting(
+        parent,
+        MenuButtonAction::CycleTextScale,
+        "accessibility.text_size",
+        &format!("{}%", (settings.text_scale * 100.).round()),
+        true,
+    );
+    style.setting(
+        parent,
+        MenuButtonAction::ToggleReduceMotion,
+        "accessibility.reduce_motion",
+        style.on_off(settings.reduce_motion),
+        true,
+    );
+    style.setting(
+        parent,
+        MenuButtonAction::ToggleCaptions,
+        "accessibility.captions",
+        style.on_off(settings.captions),
+        true,
+    );
+    // Back to the settings, rather than the main page
+    style.button(
+        parent,
+        MenuButtonAction::BackToSettings,
+        style.locale.get("common.back"),
+        true,
+    );
+}
+
+fn percentage(part: usize, whole: usize) -> String {
+    match whole {
+        0 => "-".to_string(),
+        _ => format!("{}%", part * 100 / whole),
+    }
+}
+
 fn menu_action(
-    interaction_query: Query<(&Interaction, &MenuButtonAction)>,
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &MenuButtonAction), Changed<Interaction>>,
     mut app_exit_events: EventWriter<AppExit>,
     mut game_state: ResMut<NextState<GameState>>,
+    mut page: ResMut<MenuPage>,
+    mut difficulty: ResMut<Difficulty>,
+    mut settings: ResMut<Settings>,
     is_first_run: Res<IsFirstRun>,
 ) {
     for (interaction, menu_button_action) in &interaction_query {
@@ -164,9 +678,110 @@ fn menu_action(
                 MenuButtonAction::Resume if !**is_first_run => {
                     game_state.set(GameState::Playing);
                 }
+                MenuButtonAction::Continue if save_exists() => {
+                    // The menu stays open if the save can't be loaded
+                    commands.add(|world: &mut World| match load_saved_match(world) {
+                        Ok(()) => world
+                            .resource_mut::<NextState<GameState>>()
+                            .set(GameState::Playing),
+                        Err(error) => error!("Failed to load the saved match: {error}"),
+                    });
+                }
                 MenuButtonAction::New => {
                     game_state.set(GameState::Reset);
                 }
+                MenuButtonAction::Difficulty => {
+                    *difficulty = difficulty.next();
+                    // Rebuild the page to show the new difficulty
+                    page.set_changed();
+                }
+                MenuButtonAction::Stats => {
+                    *page = MenuPage::Stats;
+                }
+                MenuButtonAction::Achievements => {
+                    *page = MenuPage::Achievements;
+                }
+                MenuButtonAction::Settings | MenuButtonAction::BackToSettings => {
+                    *page = MenuPage::Settings;
+                }
+                MenuButtonAction::ToggleParticles => {
+                    settings.particles = !settings.particles;
+                    page.set_changed();
+                }
+                MenuButtonAction::ToggleTrail => {
+                    settings.trail = !settings.trail;
+                    page.set_changed();
+                }
+                MenuButtonAction::CycleScreenShake => {
+                    // Full, half, then off
+                    settings.screen_shake = match settings.screen_shake {
+                        shake if shake > 0.5 => 0.5,
+                        shake if shake > 0. => 0.,
+                        _ => 1.,
+                    };
+                    page.set_changed();
+                }
+                MenuButtonAction::ToggleHitStop => {
+                    settings.hit_stop = !settings.hit_stop;
+                    page.set_changed();
+                }
+                MenuButtonAction::ToggleZoomPunch => {
+                    settings.zoom_punch = !settings.zoom_punch;
+                    page.set_changed();
+                }
+                MenuButtonAction::ToggleIntegerScaling => {
+                    settings.integer_scaling = !settings.integer_scaling;
+                    page.set_changed();
+                }
+                MenuButtonAction::CycleLanguage => {
+                    settings.language = settings.language.next();
+                    page.set_changed();
+                }
+                MenuButtonAction::Audio => {
+                    *page = MenuPage::Audio;
+                }
+                MenuButtonAction::CycleVolume(bus) => {
+                    let level = settings.mixer.level_mut(*bus);
+                    *level = level.next();
+                    page.set_changed();
+                }
+                MenuButtonAction::Effects => {
+                    *page = MenuPage::Effects;
+                }
+                MenuButtonAction::Accessibility => {
+                    *page = MenuPage::Accessibility;
+                }
+                MenuButtonAction::CycleTheme if !settings.colorblind => {
+                    settings.theme = settings.theme.next();
+                    page.set_changed();
+                }
+                MenuButtonAction::ToggleColorblind => {
+                    settings.colorblind = !settings.colorblind;
+                    page.set_changed();
+                }
+                MenuButtonAction::ToggleOutlines => {
+                    settings.outlines = !settings.outlines;
+                    page.set_changed();
+                }
+                MenuButtonAction::CycleTextScale => {
+                    settings.text_scale = match settings.text_scale {
+                        scale if scale < 1.25 => 1.25,
+                        scale if scale < 1.5 => 1.5,
+                        _ => 1.,
+                    };
+                    page.set_changed();
+                }
+                MenuButtonAction::ToggleReduceMotion => {
+                    settings.reduce_motion = !settings.reduce_motion;
+                    page.set_changed();
+                }
+                MenuButtonAction::ToggleCaptions => {
+                    settings.captions = !settings.captions;
+                    page.set_changed();
+                }
+                MenuButtonAction::Back => {
+                    *page = MenuPage::Main;
+                }
                 MenuButtonAction::Quit => {
                     app_exit_events.send(AppExit);
                 }
@@ -176,6 +791,57 @@ fn menu_action(
     }
 }
 
+fn load_menu_sounds(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(MenuSounds {
+        hover: asset_server.load("sounds/ui_hover.sfx.ron"),
+        click: asset_server.load("sounds/ui_click.sfx.ron"),
+        back: asset_server.load("sounds/ui_back.sfx.ron"),
+    });
+}
+
+fn play_button_sounds(
+    mut commands: Commands,
+    interaction_query: Query<
+        (&Interaction, &MenuButtonAction, Has<Disabled>),
+        Changed<Interaction>,
+    >,
+    sounds: Res<MenuSounds>,
+    settings: Res<Settings>,
+    // Pages are rebuilt after most clicks, so buttons are told apart by action rather than entity
+    mut hovered: Local<Option<MenuButtonAction>>,
+) {
+    for (interaction, action, disabled) in &interaction_query {
+        let sound = match interaction {
+            Interaction::Pressed if disabled => None,
+            Interaction::Pressed => match action {
+                MenuButtonAction::Back | MenuButtonAction::BackToSettings => Some(&sounds.back),
+                _ => Some(&sounds.click),
+            },
+            // Not again when a press is let go, or the page is rebuilt under the cursor
+            Interaction::Hovered if *hovered == Some(*action) => None,
+            Interaction::Hovered => {
+                *hovered = Some(*action);
+                (!disabled).then_some(&sounds.hover)
+            }
+            Interaction::None => {
+                if *hovered == Some(*action) {
+                    *hovered = None;
+                }
+                None
+            }
+        };
+        if let Some(sound) = sound {
+            play_sound(
+                &mut commands,
+                &settings.mixer,
+                Bus::Ui,
+                sound.clone(),
+                PlaybackSettings::DESPAWN,
+            );
+        }
+    }
+}
+
 fn teardown_menu(mut commands: Commands, despawn_query: Query<Entity, With<MenuItem>>) {
     for entity in &despawn_query {
         commands.entity(entity).despawn_recursive();
@@ -0,0 +1,148 @@
+use bevy::{audio::Volume, prelude::*};
+
+use crate::audio::{play_sound, Bus, Sound};
+use crate::ball::Ball;
+use crate::schedule::GameState;
+use crate::settings::Settings;
+use crate::{Rally, Velocity};
+
+// Seconds for a track to fade fully in or out
+const FADE_SECONDS: f32 = 1.5;
+// Rally hits, or ball speed as a multiple of the serve speed, at which each stem joins in
+const DRUMS_HITS: u32 = 4;
+const DRUMS_SPEED: f32 = 1.3;
+const LEAD_HITS: u32 = 10;
+const LEAD_SPEED: f32 = 1.7;
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_music).add_systems(
+            Update,
+            (
+                start_music.run_if(resource_exists::<MusicHandles>),
+                (choose_tracks, fade_music).chain(),
+            ),
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Track {
+    Menu,
+    // Gameplay is made of stems, layered on as it gets more intense
+    GameBass,
+    GameDrums,
+    GameLead,
+}
+
+impl Track {
+    const ALL: [Track; 4] = [
+        Track::Menu,
+        Track::GameBass,
+        Track::GameDrums,
+        Track::GameLead,
+    ];
+
+    fn path(&self) -> &'static str {
+        match self {
+            Track::Menu => "music/menu.wav",
+            Track::GameBass => "music/game_bass.wav",
+            Track::GameDrums => "music/game_drums.wav",
+            Track::GameLead => "music/game_lead.wav",
+        }
+    }
+}
+
+#[derive(Component)]
+struct Music {
+    track: Track,
+    // How far faded in, from 0 to 1
+    gain: f32,
+    target: f32,
+}
+
+// Only kept until every track has loaded and started
+#[derive(Resource)]
+struct MusicHandles(Vec<(Track, Handle<AudioSource>)>);
+
+fn load_music(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let handles = Track::ALL
+        .iter()
+        .map(|&track| (track, asset_server.load(track.path())))
+        .collect();
+    commands.insert_resource(MusicHandles(handles));
+}
+
+// Every track loops from the same moment, silently while it isn't wanted, so the stems stay in time
+fn start_music(
+    mut commands: Commands,
+    handles: Res<MusicHandles>,
+    asset_server: Res<AssetServer>,
+    settings: Res<Settings>,
+) {
+    let loaded = handles
+        .0
+        .iter()
+        .all(|(_, handle)| asset_server.is_loaded_with_dependencies(handle));
+    if !loaded {
+        return;
+    }
+    for (track, handle) in &handles.0 {
+        play_sound(
+            &mut commands,
+            &settings.mixer,
+            Bus::Music,
+            handle.clone(),
+            PlaybackSettings::LOOP.with_volume(Volume::new(0.)),
+        )
+        .insert(Music {
+            track: *track,
+            gain: 0.,
+            target: 0.,
+        });
+    }
+    commands.remove_resource::<MusicHandles>();
+}
+
+fn choose_tracks(
+    state: Res<State<GameState>>,
+    rally: Res<Rally>,
+    ball_query: Query<&Velocity, With<Ball>>,
+    mut music_query: Query<&mut Music>,
+) {
+    // The attract mode keeps the menu music going
+    let playing = *state.get() == GameState::Playing;
+    // In multiples of the serve speed
+    let speed = ball_query
+        .get_single()
+        .map(|velocity| velocity.length())
+        .unwrap_or_default();
+
+    for mut music in &mut music_query {
+        let wanted = match music.track {
+            Track::Menu => !playing,
+            Track::GameBass => playing,
+            Track::GameDrums => playing && (rally.hits >= DRUMS_HITS || speed >= DRUMS_SPEED),
+            Track::GameLead => playing && (rally.hits >= LEAD_HITS || speed >= LEAD_SPEED),
+        };
+        music.target = match wanted {
+            true => 1.,
+            false => 0.,
+        };
+    }
+}
+
+// Crossfades towards the wanted tracks. Uses real time, so hit-stop doesn't hold up a fade.
+fn fade_music(real_time: Res<Time<Real>>, mut music_query: Query<(&mut Music, &mut Sound)>) {
+    let step = real_time.delta_seconds() / FADE_SECONDS;
+    for (mut music, mut sound) in &mut music_query {
+        if music.gain == music.target {
+            continue;
+        }
+        music.gain += (music.target - music.gain).clamp(-step, step);
+        // The music bus is applied on top
+        sound.volume = music.gain;
+    }
+}
@@ -1,102 +1,127 @@
 use bevy::prelude::*;
 
-use crate::ball::Ball;
+use crate::accessibility::outline_sprite;
+use crate::console::{AddConsoleCommand, ConsoleCommand};
+use crate::controller::{
+    Controller, ControllerRegistry, CpuController, Difficulty, KeyboardController,
+};
+use crate::interpolation::Position;
 use crate::schedule::InGameSet;
+use crate::theme::Themed;
 use crate::{Collider, Side, Velocity, HEIGHT, WIDTH};
 
-const COLOR: Color = Color::WHITE;
-const SIZE: Vec2 = Vec2::new(20., 60.);
-const OFFSET: f32 = 40.;
-const SPEED: f32 = 500.;
-
-const CPU_DIFFERENCE_TOLERANCE: f32 = 7.;
+pub const SIZE: Vec2 = Vec2::new(20., 60.);
+/// Of the paddle's outer edge from the arena's side.
+pub(crate) const OFFSET: f32 = 40.;
+pub(crate) const SPEED: f32 = 500.;
 
 pub struct PaddlePlugin;
 
 impl Plugin for PaddlePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, spawn_paddles)
-            .add_systems(
-                FixedUpdate,
-                (handle_player_input, cpu_matches_ball).in_set(InGameSet::Input),
-            )
-            .add_systems(FixedUpdate, move_paddles.in_set(InGameSet::EntityUpdates));
+        app.add_console_command(ConsoleCommand {
+            name: "paddle",
+            usage: "paddle <left|right> <keyboard|controller>",
+            arguments: &["left", "right"],
+            run: paddle_command,
+        })
+        .add_systems(Startup, spawn_paddles)
+        .add_systems(FixedUpdate, move_paddles.in_set(InGameSet::EntityUpdates));
     }
 }
 
 #[derive(Component)]
-pub struct Paddle;
-#[derive(Component)]
-struct Player;
+pub struct Paddle {
+    pub side: Side,
+}
+// Marks the paddle driven by a human, so input devices can be assigned to it
 #[derive(Component)]
-struct Cpu;
+pub struct Player;
 
 fn spawn_paddles(mut commands: Commands) {
     // Right Paddle (Player)
-    commands.spawn((PaddleBundle::new(Side::Right), Player));
+    commands
+        .spawn((
+            PaddleBundle::new(Side::Right),
+            Controller::new(KeyboardController::arrows()),
+            Player,
+        ))
+        .with_children(|parent| {
+            parent.spawn(outline_sprite(SIZE));
+        });
     // Left Paddle (CPU)
-    commands.spawn((PaddleBundle::new(Side::Left), Cpu));
-}
-
-fn handle_player_input(
-    mut player_paddle_query: Query<&mut Velocity, With<Player>>,
-    input: Res<ButtonInput<KeyCode>>,
-) {
-    let mut player_velocity = player_paddle_query
-        .get_single_mut()
-        .expect("There should only be a single player");
-
-    let mut vertical_direction = 0.;
-    if input.pressed(KeyCode::ArrowUp) {
-        vertical_direction += 1.;
-    }
-    if input.pressed(KeyCode::ArrowDown) {
-        vertical_direction -= 1.;
-    }
-    player_velocity.y = vertical_direction;
-}
-
-fn cpu_matches_ball(
-    mut cpu_paddle_query: Query<(&Transform, &mut Velocity), With<Cpu>>,
-    ball_query: Query<&Transform, With<Ball>>,
-) {
-    let (cpu_transform, mut cpu_velocity) = cpu_paddle_query
-        .get_single_mut()
-        .expect("There should only be a single CPU");
-    if let Ok(ball_transform) = ball_query.get_single() {
-        let paddle_ball_height_difference =
-            cpu_transform.translation.y - ball_transform.translation.y;
-        cpu_velocity.y = match paddle_ball_height_difference {
-            diff if diff > CPU_DIFFERENCE_TOLERANCE => -diff / 100.,
-            diff if diff < -CPU_DIFFERENCE_TOLERANCE => -diff / 100.,
-            _ => 0.,
-        }
-    } else {
-        // Fixes the paddle shooting off after the ball dissapears
-        cpu_velocity.y = 0.;
-    }
+    commands
+        .spawn((
+            PaddleBundle::new(Side::Left),
+            Controller::new(CpuController::NORMAL),
+        ))
+        .with_children(|parent| {
+            parent.spawn(outline_sprite(SIZE));
+        });
 }
 
 // Updates the position of the paddle with respect to the bottom and top of the play area
 fn move_paddles(
-    mut paddle_query: Query<(&mut Transform, &Velocity), With<Paddle>>,
+    mut paddle_query: Query<(&mut Position, &Velocity), With<Paddle>>,
     time: Res<Time>,
 ) {
     let top_bound = HEIGHT / 2.0 - SIZE.y / 2.0;
     let bottom_bound = -(HEIGHT / 2.0) + SIZE.y / 2.0;
-    for (mut paddle_transform, paddle_velocity) in &mut paddle_query {
-        let new_position =
-            paddle_transform.translation.y + (paddle_velocity.y * SPEED * time.delta_seconds());
-        paddle_transform.translation.y = new_position.clamp(bottom_bound, top_bound);
+    for (mut paddle_position, paddle_velocity) in &mut paddle_query {
+        let new_position = paddle_position.y + (paddle_velocity.y * SPEED * time.delta_seconds());
+        paddle_position.y = new_position.clamp(bottom_bound, top_bound);
     }
 }
 
+// Hands either paddle to the keyboard or to any registered AI, such as for watching two AIs play
+fn paddle_command(world: &mut World, arguments: &[&str]) -> Result<String, String> {
+    let usage = || "usage: paddle <left|right> <keyboard|controller>".to_string();
+    let [side, name] = arguments else {
+        return Err(usage());
+    };
+    let side = match *side {
+        "left" => Side::Left,
+        "right" => Side::Right,
+        _ => return Err(usage()),
+    };
+    let controller = match *name {
+        "keyboard" => Controller::new(KeyboardController::arrows()),
+        name => {
+            let registry = world.resource::<ControllerRegistry>();
+            let Some(factory) = registry.get(name) else {
+                let names: Vec<&str> = registry.names().collect();
+                return Err(format!(
+                    "no controller called {name}, try {}",
+                    names.join(", ")
+                ));
+            };
+            Controller(factory(fastrand::u64(..)))
+        }
+    };
+    let mut paddle_query = world.query::<(Entity, &Paddle)>();
+    let Some(entity) = paddle_query
+        .iter(world)
+        .find(|(_, paddle)| paddle.side == side)
+        .map(|(entity, _)| entity)
+    else {
+        return Err(format!("there is no {side:?} paddle"));
+    };
+    // Whatever is swapped in isn't playing at any difficulty
+    world
+        .entity_mut(entity)
+        .insert(controller)
+        .remove::<Difficulty>();
+    Ok(format!("The {side:?} paddle is now controlled by {name}"))
+}
+
 #[derive(Bundle)]
 struct PaddleBundle {
     sprite_bundle: SpriteBundle,
+    position: Position,
     collider: Collider,
     velocity: Velocity,
     paddle: Paddle,
+    themed: Themed,
 }
 
 impl PaddleBundle {
@@ -106,16 +131,17 @@ impl PaddleBundle {
         PaddleBundle {
             sprite_bundle: SpriteBundle {
                 sprite: Sprite {
-                    color: COLOR,
                     custom_size: Some(SIZE),
                     ..default()
                 },
                 transform: Transform::from_translation(center),
                 ..default()
             },
+            position: Position::new(center),
             collider: Collider { bounding_box: SIZE },
             velocity: Velocity(Vec3::ZERO),
-            paddle: Paddle,
+            paddle: Paddle { side },
+            themed: Themed::Paddle,
         }
     }
 }
@@ -128,9 +154,10 @@ fn position(side: Side) -> Vec3 {
     }
 }
 
-pub fn reset_paddles(mut paddle_query: Query<(&mut Transform, &mut Velocity), With<Paddle>>) {
-    for (mut paddle_transform, mut paddle_velocity) in &mut paddle_query {
-        paddle_transform.translation.y = 0.;
+pub(crate) fn reset_paddles(mut paddle_query: Query<(&mut Position, &mut Velocity), With<Paddle>>) {
+    for (mut paddle_position, mut paddle_velocity) in &mut paddle_query {
+        let center = Vec3::new(paddle_position.x, 0., paddle_position.z);
+        paddle_position.teleport(center);
         **paddle_velocity = Vec3::ZERO;
     }
 }
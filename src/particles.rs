@@ -0,0 +1,214 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::ball::{self, Ball};
+use crate::events::{PaddleHit, WallBounce};
+use crate::interpolation::Position;
+use crate::schedule::InGameSet;
+use crate::settings::Settings;
+use crate::theme::ActiveTheme;
+use crate::wall::GoalEvent;
+use crate::Side;
+
+const SIZE: f32 = 4.;
+const Z: f32 = 1.;
+
+// Sparks per hit at the base ball speed, faster hits throw more and further
+const SPARK_COUNT: f32 = 8.;
+const SPARK_SPEED: f32 = 150.;
+const SPARK_SPREAD: f32 = 1.2;
+const SPARK_LIFETIME: f32 = 0.3;
+
+const BURST_COUNT: usize = 40;
+const BURST_SPEED: f32 = 250.;
+const BURST_LIFETIME: f32 = 0.6;
+
+pub struct ParticlesPlugin;
+
+impl Plugin for ParticlesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ParticlePool>()
+            .add_systems(
+                FixedUpdate,
+                (emit_sparks, emit_goal_burst)
+                    .after(InGameSet::CollisionDetection)
+                    .run_if(|settings: Res<Settings>| settings.particles),
+            )
+            .add_systems(Update, update_particles);
+    }
+}
+
+#[derive(Component)]
+struct Particle {
+    velocity: Vec2,
+    color: Color,
+    lifetime: Timer,
+}
+
+// Finished particles are hidden and kept for reuse, rather than despawned
+#[derive(Resource, Default)]
+struct ParticlePool {
+    free: Vec<Entity>,
+    alive: usize,
+}
+
+struct Emitter<'a, 'w, 's> {
+    commands: Commands<'w, 's>,
+    pool: &'a mut ParticlePool,
+    max_particles: usize,
+}
+
+impl Emitter<'_, '_, '_> {
+    fn emit(&mut self, position: Vec2, velocity: Vec2, color: Color, lifetime: f32) {
+        if self.pool.alive >= self.max_particles {
+            return;
+        }
+        self.pool.alive += 1;
+
+        let particle = Particle {
+            velocity,
+            color,
+            lifetime: Timer::from_seconds(lifetime, TimerMode::Once),
+        };
+        let sprite = SpriteBundle {
+            sprite: Sprite {
+                color,
+                custom_size: Some(Vec2::splat(SIZE)),
+                ..default()
+            },
+            transform: Transform::from_translation(position.extend(Z)),
+            ..default()
+        };
+        match self.pool.free.pop() {
+            Some(entity) => {
+                self.commands.entity(entity).insert((sprite, particle));
+            }
+            None => {
+                self.commands.spawn((sprite, particle));
+            }
+        }
+    }
+
+    // Particles fanned out around a direction, with some randomness to speed and angle
+    fn spray(
+        &mut self,
+        position: Vec2,
+        direction: f32,
+        spread: f32,
+        count: usize,
+        speed: f32,
+        color: Color,
+        lifetime: f32,
+    ) {
+        for _ in 0..count {
+            let angle = direction + (fastrand::f32() - 0.5) * spread;
+            let speed = speed * (0.5 + fastrand::f32());
+            let velocity = Vec2::from_angle(angle) * speed;
+            self.emit(position, velocity, color, lifetime);
+        }
+    }
+}
+
+fn emit_sparks(
+    commands: Commands,
+    mut pool: ResMut<ParticlePool>,
+    settings: Res<Settings>,
+    theme: Res<ActiveTheme>,
+    mut paddle_hit_events: EventReader<PaddleHit>,
+    mut wall_bounce_events: EventReader<WallBounce>,
+) {
+    let mut emitter = Emitter {
+        commands,
+        pool: &mut pool,
+        max_particles: settings.max_particles,
+    };
+    // Sparks fly back into the court, away from whatever the ball hit
+    let hits = paddle_hit_events.read().map(|hit| {
+        let direction = match hit.side {
+            Side::Left => 0.,
+            Side::Right => TAU / 2.,
+        };
+        (hit.contact, direction, hit.ball_speed)
+    });
+    let bounces = wall_bounce_events.read().map(|bounce| {
+        let direction = -bounce.contact.y.signum() * TAU / 4.;
+        (bounce.contact, direction, bounce.ball_speed)
+    });
+    for (contact, direction, ball_speed) in hits.chain(bounces) {
+        let strength = ball_speed / ball::SPEED;
+        emitter.spray(
+            contact,
+            direction,
+            SPARK_SPREAD,
+            (SPARK_COUNT * strength).round() as usize,
+            SPARK_SPEED * strength,
+            theme.ball,
+            SPARK_LIFETIME,
+        );
+    }
+}
+
+fn emit_goal_burst(
+    commands: Commands,
+    mut pool: ResMut<ParticlePool>,
+    settings: Res<Settings>,
+    theme: Res<ActiveTheme>,
+    mut goal_events: EventReader<GoalEvent>,
+    ball_query: Query<&Position, With<Ball>>,
+) {
+    let mut emitter = Emitter {
+        commands,
+        pool: &mut pool,
+        max_particles: settings.max_particles,
+    };
+    // The ball is only put back in the center on the next tick
+    let Ok(ball_position) = ball_query.get_single() else {
+        return;
+    };
+    // A screen-filling flash of particles
+    if settings.reduce_motion {
+        goal_events.clear();
+        return;
+    }
+    for _ in goal_events.read() {
+        emitter.spray(
+            ball_position.truncate(),
+            0.,
+            TAU,
+            BURST_COUNT,
+            BURST_SPEED,
+            theme.score,
+            BURST_LIFETIME,
+        );
+    }
+}
+
+fn update_particles(
+    mut pool: ResMut<ParticlePool>,
+    mut particle_query: Query<(
+        Entity,
+        &mut Particle,
+        &mut Transform,
+        &mut Sprite,
+        &mut Visibility,
+    )>,
+    time: Res<Time>,
+) {
+    for (entity, mut particle, mut transform, mut sprite, mut visibility) in &mut particle_query {
+        if particle.lifetime.finished() {
+            continue;
+        }
+        if particle.lifetime.tick(time.delta()).just_finished() {
+            *visibility = Visibility::Hidden;
+            pool.free.push(entity);
+            pool.alive -= 1;
+            continue;
+        }
+        transform.translation += (particle.velocity * time.delta_seconds()).extend(0.);
+        // Fade out over the whole lifetime
+        sprite.color = particle
+            .color
+            .with_a(particle.color.a() * particle.lifetime.fraction_remaining());
+    }
+}
@@ -0,0 +1,318 @@
+use std::time::Duration;
+
+use bevy::{app::AppExit, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::attract::SuspendedMatch;
+use crate::ball::Ball;
+use crate::console::{AddConsoleCommand, ConsoleCommand};
+use crate::controller::{Controller, ControllerRegistry, CpuController, Difficulty};
+use crate::events::MatchEnded;
+use crate::interpolation::Position;
+use crate::paddle::{Paddle, Player};
+use crate::schedule::{GameState, InGameSet};
+use crate::score::Score;
+use crate::{storage, IsFirstRun, MatchRules, ServeDirection, ServeTimer, Side, Velocity};
+
+pub const SAVE_FILE: &str = "save.ron";
+const SAVE_VERSION: u32 = 1;
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_console_command(ConsoleCommand {
+            name: "save",
+            usage: "save [name]",
+            arguments: &[],
+            run: save_command,
+        })
+        .add_console_command(ConsoleCommand {
+            name: "load",
+            usage: "load [name]",
+            arguments: &[],
+            run: load_command,
+        })
+        .add_console_command(ConsoleCommand {
+            name: "replay",
+            usage: "replay <save|load> <name>",
+            arguments: &["save", "load"],
+            run: replay_command,
+        })
+        .add_systems(Update, quick_save.run_if(in_state(GameState::Playing)))
+        .add_systems(
+            FixedUpdate,
+            discard_save
+                .after(InGameSet::EntityUpdates)
+                .run_if(in_state(GameState::Playing).and_then(on_event::<MatchEnded>())),
+        )
+        .add_systems(Last, save_on_exit);
+    }
+}
+
+/// Everything needed to pick a match back up exactly where it was left.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MatchSnapshot {
+    pub score: (u32, u32),
+    pub ball: Option<BallSnapshot>,
+    pub paddles: Vec<PaddleSnapshot>,
+    pub serve_direction: ServeDirection,
+    pub serve_elapsed: f32,
+    pub rules: MatchRules,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BallSnapshot {
+    pub position: [f32; 3],
+    pub velocity: [f32; 3],
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PaddleSnapshot {
+    pub side: Side,
+    pub position: [f32; 3],
+    /// The `PaddleController::name` of whatever was driving the paddle.
+    pub controller: String,
+    /// Of a CPU opponent, which is rebuilt from this rather than `controller`.
+    #[serde(default)]
+    pub difficulty: Option<Difficulty>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SaveGame {
+    version: u32,
+    snapshot: MatchSnapshot,
+}
+
+impl MatchSnapshot {
+    pub fn capture(world: &mut World) -> Self {
+        let score = world.resource::<Score>();
+        let score = (score.left, score.right);
+
+        let mut ball_query = world.query_filtered::<(&Position, &Velocity), With<Ball>>();
+        let ball = ball_query
+            .get_single(world)
+            .ok()
+            .map(|(position, velocity)| BallSnapshot {
+                position: position.to_array(),
+                velocity: velocity.to_array(),
+            });
+
+        let mut paddle_query =
+            world.query::<(&Paddle, &Position, Option<&Controller>, Option<&Difficulty>)>();
+        let paddles = paddle_query
+            .iter(world)
+            .map(
+                |(paddle, position, controller, difficulty)| PaddleSnapshot {
+                    side: paddle.side,
+                    position: position.to_array(),
+                    controller: controller
+                        .map(|controller| controller.0.name().to_string())
+                        .unwrap_or_default(),
+                    difficulty: difficulty.copied(),
+                },
+            )
+            .collect();
+
+        Self {
+            score,
+            ball,
+            paddles,
+            serve_direction: *world.resource::<ServeDirection>(),
+            serve_elapsed: world.resource::<ServeTimer>().timer.elapsed_secs(),
+            rules: world.resource::<MatchRules>().clone(),
+        }
+    }
+
+    /// Restores positions, score and serve state. Controllers are left alone, see `apply_controllers`.
+    pub fn apply(&self, world: &mut World) {
+        let mut score = world.resource_mut::<Score>();
+        (score.left, score.right) = self.score;
+
+        let mut ball_query = world.query_filtered::<(&mut Position, &mut Velocity), With<Ball>>();
+        if let (Ok((mut position, mut velocity)), Some(ball)) =
+            (ball_query.get_single_mut(world), &self.ball)
+        {
+            position.teleport(Vec3::from_array(ball.position));
+            velocity.0 = Vec3::from_array(ball.velocity);
+        }
+
+        let mut paddle_query = world.query::<(&Paddle, &mut Position, &mut Velocity)>();
+        for (paddle, mut position, mut velocity) in paddle_query.iter_mut(world) {
+            if let Some(saved) = self.paddles.iter().find(|saved| saved.side == paddle.side) {
+                position.teleport(Vec3::from_array(saved.position));
+                velocity.0 = Vec3::ZERO;
+            }
+        }
+
+        *world.resource_mut::<ServeDirection>() = self.serve_direction;
+        let mut serve_timer = world.resource_mut::<ServeTimer>();
+        serve_timer.timer.reset();
+        serve_timer
+            .timer
+            .set_elapsed(Duration::from_secs_f32(self.serve_elapsed));
+        // Marks the timer finished if the ball had already been served, so it isn't served again
+        serve_timer.timer.tick(Duration::ZERO);
+
+        world.insert_resource(self.rules.clone());
+    }
+
+    /// Recreates the saved AIs from the registry. Human paddles keep their current input device.
+    pub fn apply_controllers(&self, world: &mut World) {
+        let mut paddle_query = world.query_filtered::<(Entity, &Paddle), Without<Player>>();
+        let paddles: Vec<(Entity, Side)> = paddle_query
+            .iter(world)
+            .map(|(entity, paddle)| (entity, paddle.side))
+            .collect();
+
+        for (entity, side) in paddles {
+            let Some(saved) = self.paddles.iter().find(|saved| saved.side == side) else {
+                continue;
+            };
+            if let Some(difficulty) = saved.difficulty {
+                world
+                    .entity_mut(entity)
+                    .insert((difficulty.controller(), difficulty));
+                continue;
+            }
+            let controller = match world
+                .resource::<ControllerRegistry>()
+                .get(&saved.controller)
+            {
+                // Seeded afresh, the same as when a match starts
+                Some(factory) => Controller(factory(fastrand::u64(..))),
+                None => {
+                    warn!(
+                        "Unknown controller {} in save, using the CPU",
+                        saved.controller
+                    );
+                    Controller::new(CpuController::NORMAL)
+                }
+            };
+            world
+                .entity_mut(entity)
+                .insert(controller)
+                .remove::<Difficulty>();
+        }
+    }
+}
+
+pub fn save_exists() -> bool {
+    storage::exists(SAVE_FILE)
+}
+
+fn write_save(file_name: &str, snapshot: MatchSnapshot) -> Result<(), String> {
+    let save = SaveGame {
+        version: SAVE_VERSION,
+        snapshot,
+    };
+    match storage::save(file_name, &save) {
+        Ok(()) => {
+            info!(
+                "Saved match to {}",
+                storage::data_dir().join(file_name).display()
+            );
+            Ok(())
+        }
+        Err(error) => {
+            error!("Failed to save match: {error}");
+            Err(error.to_string())
+        }
+    }
+}
+
+/// Loads the saved match into the world. Nothing changes if it can't be loaded.
+pub fn load_saved_match(world: &mut World) -> Result<(), String> {
+    load_match(world, SAVE_FILE)
+}
+
+fn load_match(world: &mut World, file_name: &str) -> Result<(), String> {
+    let Some(save) = storage::load::<SaveGame>(file_name) else {
+        return Err(format!("{file_name} couldn't be read"));
+    };
+    if save.version != SAVE_VERSION {
+        return Err(format!(
+            "{file_name} is from unsupported version {}",
+            save.version
+        ));
+    }
+    save.snapshot.apply(world);
+    save.snapshot.apply_controllers(world);
+    Ok(())
+}
+
+fn quick_save(world: &mut World) {
+    if world
+        .resource::<ButtonInput<KeyCode>>()
+        .just_pressed(KeyCode::F5)
+    {
+        write_save(SAVE_FILE, MatchSnapshot::capture(world)).ok();
+    }
+}
+
+// The quick save, or a file of its own for a named save so it isn't overwritten by playing on
+fn save_file(arguments: &[&str]) -> Result<String, String> {
+    match arguments {
+        [] => Ok(SAVE_FILE.to_string()),
+        [name]
+            if name
+                .chars()
+                .all(|char| char.is_ascii_alphanumeric() || char == '-' || char == '_') =>
+        {
+            Ok(format!("save-{name}.ron"))
+        }
+        [name] => Err(format!(
+            "{name} can't be a save name, use letters, numbers, - and _"
+        )),
+        _ => Err("too many arguments, a save name is one word".to_string()),
+    }
+}
+
+fn save_command(world: &mut World, arguments: &[&str]) -> Result<String, String> {
+    let file_name = save_file(arguments)?;
+    if *world.resource::<State<GameState>>() != GameState::Playing {
+        return Err("there is no match being played to save".to_string());
+    }
+    write_save(&file_name, MatchSnapshot::capture(world))?;
+    Ok(format!("Saved the match to {file_name}"))
+}
+
+fn load_command(world: &mut World, arguments: &[&str]) -> Result<String, String> {
+    let file_name = save_file(arguments)?;
+    if !storage::exists(&file_name) {
+        return Err(format!("there is no saved match in {file_name}"));
+    }
+    load_match(world, &file_name)?;
+    world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Playing);
+    Ok(format!("Loaded the match in {file_name}"))
+}
+
+// There are no recordings yet, so a replay is the named save of the match
+fn replay_command(world: &mut World, arguments: &[&str]) -> Result<String, String> {
+    match arguments {
+        ["save", name] => save_command(world, &[name]),
+        ["load", name] => load_command(world, &[name]),
+        _ => Err("usage: replay <save|load> <name>".to_string()),
+    }
+}
+
+// A finished match can't be continued
+fn discard_save() {
+    if let Err(error) = storage::remove(SAVE_FILE) {
+        error!("Failed to remove the finished match's save: {error}");
+    }
+}
+
+fn save_on_exit(world: &mut World) {
+    if world.resource::<Events<AppExit>>().is_empty() || **world.resource::<IsFirstRun>() {
+        return;
+    }
+    // While the attract mode runs, the real match is tucked away
+    let snapshot = match world.get_resource::<SuspendedMatch>() {
+        Some(suspended) => suspended.snapshot.clone(),
+        None => MatchSnapshot::capture(world),
+    };
+    write_save(SAVE_FILE, snapshot).ok();
+}
@@ -1,11 +1,15 @@
 use bevy::prelude::*;
 
+use crate::console::{AddConsoleCommand, ConsoleCommand};
+
 #[derive(Clone, Eq, PartialEq, Debug, Hash, Default, States)]
 pub enum GameState {
     #[default]
     Menu,
     Reset,
     Playing,
+    // A CPU-vs-CPU demo match shown while the menu sits idle
+    Attract,
 }
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
@@ -21,6 +25,12 @@ pub struct SchedulePlugin;
 impl Plugin for SchedulePlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<GameState>()
+            .add_console_command(ConsoleCommand {
+                name: "state",
+                usage: "state <menu|reset|playing|attract>",
+                arguments: &["menu", "reset", "playing", "attract"],
+                run: state_command,
+            })
             .configure_sets(
                 FixedUpdate,
                 (
@@ -31,7 +41,7 @@ impl Plugin for SchedulePlugin {
                     InGameSet::CollisionDetection,
                 )
                     .chain()
-                    .run_if(in_state(GameState::Playing)),
+                    .run_if(in_state(GameState::Playing).or_else(in_state(GameState::Attract))),
             )
             .add_systems(
                 FixedUpdate,
@@ -41,3 +51,16 @@ impl Plugin for SchedulePlugin {
             );
     }
 }
+
+fn state_command(world: &mut World, arguments: &[&str]) -> Result<String, String> {
+    let state = match arguments {
+        ["menu"] => GameState::Menu,
+        ["reset"] => GameState::Reset,
+        ["playing"] => GameState::Playing,
+        ["attract"] => GameState::Attract,
+        _ => return Err("usage: state <menu|reset|playing|attract>".to_string()),
+    };
+    let message = format!("Switching to {state:?}");
+    world.resource_mut::<NextState<GameState>>().set(state);
+    Ok(message)
+}
@@ -1,10 +1,16 @@
 use bevy::prelude::*;
 
-use crate::{schedule::InGameSet, wall::GoalEvent, Side, HEIGHT};
+use crate::{
+    console::{AddConsoleCommand, ConsoleCommand},
+    events::{MatchEnded, PointScored, RallyEnded},
+    schedule::InGameSet,
+    theme::Themed,
+    wall::GoalEvent,
+    MatchRules, Rally, Side, HEIGHT,
+};
 
 // Scoreboard
 const SCOREBOARD_FONT_SIZE: f32 = 72.;
-const SCORE_COLOR: Color = Color::GRAY;
 const SCORE_GAP: f32 = 60.;
 
 #[derive(Resource)]
@@ -23,23 +29,51 @@ pub struct ScorePlugin;
 impl Plugin for ScorePlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(Score { left: 0, right: 0 })
+            .add_console_command(ConsoleCommand {
+                name: "score",
+                usage: "score <left> <right>",
+                arguments: &[],
+                run: score_command,
+            })
             .add_systems(Startup, setup_scoreboard_worldspace)
             .add_systems(
                 FixedUpdate,
-                (update_scoreboard, update_scores)
+                (update_scoreboard, update_scores, check_match_over)
                     .chain()
                     .in_set(InGameSet::EntityUpdates),
             );
     }
 }
 
+// Reaching the points needed to win ends the match, the same as scoring them would
+fn score_command(world: &mut World, arguments: &[&str]) -> Result<String, String> {
+    let [left, right] = arguments else {
+        return Err("usage: score <left> <right>".to_string());
+    };
+    let parse = |points: &str| {
+        points
+            .parse::<u32>()
+            .map_err(|_| format!("{points} isn't a score"))
+    };
+    let score = Score {
+        left: parse(left)?,
+        right: parse(right)?,
+    };
+    let mut message = format!("Score set to {} - {}", score.left, score.right);
+    let points_to_win = world.resource::<MatchRules>().points_to_win;
+    if score.left.max(score.right) >= points_to_win {
+        message += &format!(", {points_to_win} points wins so the match is over");
+    }
+    world.insert_resource(score);
+    Ok(message)
+}
+
 // Equivalent to the old way, but in world space so scores can be behind the ball
-fn setup_scoreboard_worldspace(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let font = asset_server.load("fonts/PixelifySans-VariableFont_wght.ttf");
+fn setup_scoreboard_worldspace(mut commands: Commands) {
+    // The theme fills in the font and color
     let text_style = TextStyle {
-        font: font.clone(),
         font_size: SCOREBOARD_FONT_SIZE,
-        color: SCORE_COLOR,
+        ..default()
     };
     commands.spawn((
         Text2dBundle {
@@ -53,6 +87,7 @@ fn setup_scoreboard_worldspace(mut commands: Commands, asset_server: Res<AssetSe
             ..default()
         },
         ScoreText { side: Side::Left },
+        Themed::Score,
     ));
     commands.spawn((
         Text2dBundle {
@@ -66,6 +101,7 @@ fn setup_scoreboard_worldspace(mut commands: Commands, asset_server: Res<AssetSe
             ..default()
         },
         ScoreText { side: Side::Right },
+        Themed::Score,
     ));
 }
 
@@ -80,13 +116,48 @@ fn update_scoreboard(scoreboard: Res<Score>, mut query: Query<(&mut Text, &Score
     }
 }
 
-fn update_scores(mut goal_events: EventReader<GoalEvent>, mut current_scores: ResMut<Score>) {
+fn update_scores(
+    mut goal_events: EventReader<GoalEvent>,
+    mut current_scores: ResMut<Score>,
+    rally: Res<Rally>,
+    mut point_events: EventWriter<PointScored>,
+    mut rally_events: EventWriter<RallyEnded>,
+) {
     for event in goal_events.read() {
         // We apply the score to the opposite side to where the goal was scored
-        match event.0 {
-            Side::Left => current_scores.right += 1,
-            Side::Right => current_scores.left += 1,
-        }
+        let (scorer, new_score) = match event.0 {
+            Side::Left => {
+                current_scores.right += 1;
+                (Side::Right, current_scores.right)
+            }
+            Side::Right => {
+                current_scores.left += 1;
+                (Side::Left, current_scores.left)
+            }
+        };
+        point_events.send(PointScored { scorer, new_score });
+        rally_events.send(RallyEnded {
+            hits: rally.hits,
+            duration: rally.duration,
+        });
+    }
+}
+
+fn check_match_over(
+    scoreboard: Res<Score>,
+    rules: Res<MatchRules>,
+    mut match_ended_events: EventWriter<MatchEnded>,
+) {
+    // Only look when a goal was just scored, so the match ends exactly once
+    if !scoreboard.is_changed() {
+        return;
+    }
+    if scoreboard.left >= rules.points_to_win {
+        match_ended_events.send(MatchEnded { winner: Side::Left });
+    } else if scoreboard.right >= rules.points_to_win {
+        match_ended_events.send(MatchEnded {
+            winner: Side::Right,
+        });
     }
 }
 
@@ -0,0 +1,108 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::audio::Mixer;
+use crate::locale::Language;
+use crate::storage;
+use crate::theme::ThemeName;
+
+pub const SETTINGS_FILE: &str = "settings.ron";
+const SETTINGS_VERSION: u32 = 1;
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_settings())
+            .add_systems(Update, save_settings.run_if(resource_changed::<Settings>));
+    }
+}
+
+/// Player preferences, saved whenever they change.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+// Fields missing from older files keep their defaults
+#[serde(default)]
+pub struct Settings {
+    pub particles: bool,
+    /// The most particles alive at once, new ones are dropped past it.
+    pub max_particles: usize,
+    pub trail: bool,
+    /// In fixed ticks, up to `trail::MAX_LENGTH`.
+    pub trail_length: usize,
+    /// Screen shake intensity, from 0 for none to 1 for full.
+    pub screen_shake: f32,
+    pub hit_stop: bool,
+    pub zoom_punch: bool,
+    /// Scale the view by whole multiples only, for crisp pixels.
+    pub integer_scaling: bool,
+    pub theme: ThemeName,
+    /// Outline the ball, paddles and walls in the theme's outline color.
+    pub outlines: bool,
+    /// Use the colorblind-safe palette instead of the selected theme.
+    pub colorblind: bool,
+    /// Multiplies the size of all menu and overlay text.
+    pub text_scale: f32,
+    /// Turns off screen shake, zoom punch, hit-stop and goal bursts, whatever their settings.
+    pub reduce_motion: bool,
+    /// Show sound effects as text.
+    pub captions: bool,
+    pub language: Language,
+    pub mixer: Mixer,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            particles: true,
+            max_particles: 500,
+            trail: true,
+            trail_length: 12,
+            screen_shake: 1.,
+            hit_stop: true,
+            zoom_punch: true,
+            integer_scaling: false,
+            theme: ThemeName::Classic,
+            outlines: false,
+            colorblind: false,
+            text_scale: 1.,
+            reduce_motion: false,
+            captions: false,
+            language: Language::English,
+            mixer: Mixer::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SettingsFile {
+    version: u32,
+    settings: Settings,
+}
+
+fn load_settings() -> Settings {
+    match storage::load::<SettingsFile>(SETTINGS_FILE) {
+        Some(file) if file.version == SETTINGS_VERSION => file.settings,
+        Some(file) => {
+            warn!(
+                "Ignoring settings from unsupported version {}",
+                file.version
+            );
+            Settings::default()
+        }
+        None => Settings::default(),
+    }
+}
+
+fn save_settings(settings: Res<Settings>) {
+    // Nothing to write when they were only just loaded
+    if settings.is_added() {
+        return;
+    }
+    let file = SettingsFile {
+        version: SETTINGS_VERSION,
+        settings: settings.clone(),
+    };
+    if let Err(error) = storage::save(SETTINGS_FILE, &file) {
+        error!("Failed to save settings: {error}");
+    }
+}
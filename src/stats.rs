@@ -0,0 +1,272 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::controller::Difficulty;
+use crate::events::{MatchEnded, PaddleHit, RallyEnded};
+use crate::paddle::{Paddle, Player};
+use crate::schedule::{GameState, InGameSet};
+use crate::score::Score;
+use crate::{storage, Side};
+
+pub const STATS_FILE: &str = "stats.ron";
+const STATS_VERSION: u32 = 1;
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_player_stats())
+            .init_resource::<MatchTracker>()
+            .add_systems(OnEnter(GameState::Reset), reset_tracker)
+            .add_systems(
+                FixedUpdate,
+                (
+                    track_match
+                        .after(InGameSet::CollisionDetection)
+                        .run_if(in_state(GameState::Playing)),
+                    record_match
+                        .after(InGameSet::EntityUpdates)
+                        .after(track_match)
+                        .run_if(in_state(GameState::Playing).and_then(on_event::<MatchEnded>())),
+                ),
+            );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MatchMode {
+    VersusCpu,
+}
+
+impl MatchMode {
+    pub fn key(&self) -> &'static str {
+        match self {
+            MatchMode::VersusCpu => "mode.versus_cpu",
+        }
+    }
+}
+
+/// A single completed match, from the human player's point of view.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchRecord {
+    /// Seconds since the Unix epoch when the match started.
+    pub date: u64,
+    pub mode: MatchMode,
+    pub difficulty: Difficulty,
+    pub player_score: u32,
+    pub opponent_score: u32,
+    pub duration_secs: f32,
+    pub longest_rally: u32,
+    pub hits: u32,
+    pub fastest_ball_speed: f32,
+}
+
+impl MatchRecord {
+    pub fn won(&self) -> bool {
+        self.player_score > self.opponent_score
+    }
+}
+
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PlayerStats {
+    /// Oldest first.
+    pub matches: Vec<MatchRecord>,
+}
+
+impl PlayerStats {
+    pub fn wins(&self) -> usize {
+        self.matches.iter().filter(|record| record.won()).count()
+    }
+
+    /// Matches won and played against the given difficulty.
+    pub fn record_against(&self, difficulty: Difficulty) -> (usize, usize) {
+        let played = self
+            .matches
+            .iter()
+            .filter(|record| record.difficulty == difficulty);
+        let won = played.clone().filter(|record| record.won()).count();
+        (won, played.count())
+    }
+
+    /// The most recent matches, newest first.
+    pub fn recent(&self, count: usize) -> impl Iterator<Item = &MatchRecord> {
+        self.matches.iter().rev().take(count)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct StatsFile {
+    version: u32,
+    stats: PlayerStats,
+}
+
+// Just enough of any version of the file to know how to read the rest
+#[derive(Deserialize)]
+struct VersionProbe {
+    // Version 0 had no version, it was the statistics on their own
+    #[serde(default)]
+    version: u32,
+}
+
+fn load_player_stats() -> PlayerStats {
+    let Some(contents) = storage::read(STATS_FILE) else {
+        return PlayerStats::default();
+    };
+    match migrate(&contents) {
+        Ok(stats) => stats,
+        Err(error) => {
+            warn!("Starting fresh statistics, the old file couldn't be read: {error}");
+            if let Err(error) = storage::backup(STATS_FILE) {
+                error!("Failed to back up the old statistics: {error}");
+            }
+            PlayerStats::default()
+        }
+    }
+}
+
+// Brings any older version of the file up to date
+fn migrate(contents: &str) -> Result<PlayerStats, String> {
+    let probe: VersionProbe = ron::from_str(contents).map_err(|error| error.to_string())?;
+    let stats = match probe.version {
+        0 => ron::from_str::<PlayerStats>(contents),
+        STATS_VERSION => ron::from_str::<StatsFile>(contents).map(|file| file.stats),
+        version => return Err(format!("version {version} is newer than this game")),
+    };
+    stats.map_err(|error| error.to_string())
+}
+
+fn save_player_stats(stats: &PlayerStats) {
+    let file = StatsFile {
+        version: STATS_VERSION,
+        stats: stats.clone(),
+    };
+    if let Err(error) = storage::save(STATS_FILE, &file) {
+        error!("Failed to save statistics: {error}");
+    }
+}
+
+// Running totals for the match being played
+#[derive(Resource, Default)]
+struct MatchTracker {
+    started: u64,
+    duration_secs: f32,
+    hits: u32,
+    longest_rally: u32,
+    fastest_ball_speed: f32,
+}
+
+/// Seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+fn reset_tracker(mut tracker: ResMut<MatchTracker>) {
+    *tracker = MatchTracker::default();
+}
+
+fn track_match(
+    mut tracker: ResMut<MatchTracker>,
+    mut paddle_hit_events: EventReader<PaddleHit>,
+    mut rally_events: EventReader<RallyEnded>,
+    time: Res<Time>,
+) {
+    // Matches continued from a save start counting from here
+    if tracker.started == 0 {
+        tracker.started = now();
+    }
+    tracker.duration_secs += time.delta_seconds();
+
+    for hit in paddle_hit_events.read() {
+        tracker.hits += 1;
+        tracker.fastest_ball_speed = tracker.fastest_ball_speed.max(hit.ball_speed);
+    }
+    for rally in rally_events.read() {
+        tracker.longest_rally = tracker.longest_rally.max(rally.hits);
+    }
+}
+
+fn record_match(
+    mut stats: ResMut<PlayerStats>,
+    tracker: Res<MatchTracker>,
+    scoreboard: Res<Score>,
+    player_query: Query<&Paddle, With<Player>>,
+    opponent_query: Query<&Difficulty, (With<Paddle>, Without<Player>)>,
+) {
+    // Only matches against the CPU at one of the difficulties count
+    let Ok(&difficulty) = opponent_query.get_single() else {
+        return;
+    };
+    let player_side = player_query
+        .get_single()
+        .map(|paddle| paddle.side)
+        .unwrap_or(Side::Right);
+    let (player_score, opponent_score) = match player_side {
+        Side::Left => (scoreboard.left, scoreboard.right),
+        Side::Right => (scoreboard.right, scoreboard.left),
+    };
+    stats.matches.push(MatchRecord {
+        date: tracker.started,
+        mode: MatchMode::VersusCpu,
+        difficulty,
+        player_score,
+        opponent_score,
+        duration_secs: tracker.duration_secs,
+        longest_rally: tracker.longest_rally,
+        hits: tracker.hits,
+        fastest_ball_speed: tracker.fastest_ball_speed,
+    });
+    save_player_stats(&stats);
+}
+
+/// Formats seconds since the Unix epoch as a `YYYY-MM-DD` date.
+pub fn format_date(timestamp: u64) -> String {
+    // Howard Hinnant's days-to-civil algorithm
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> String {
+        let path = format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR"));
+        std::fs::read_to_string(&path).unwrap_or_else(|error| panic!("{path}: {error}"))
+    }
+
+    #[test]
+    fn upgrades_version_0() {
+        let stats = migrate(&fixture("stats-v0.ron")).unwrap();
+        assert_eq!(stats.matches.len(), 2);
+        assert_eq!(stats.wins(), 1);
+        assert_eq!(stats.record_against(Difficulty::Hard), (0, 1));
+    }
+
+    #[test]
+    fn reads_back_the_current_version() {
+        let file = StatsFile {
+            version: STATS_VERSION,
+            stats: migrate(&fixture("stats-v0.ron")).unwrap(),
+        };
+        let contents = ron::to_string(&file).unwrap();
+        assert_eq!(migrate(&contents).unwrap().matches.len(), 2);
+    }
+}
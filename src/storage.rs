@@ -0,0 +1,62 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+const APP_DIRECTORY: &str = "bevy-pong";
+
+/// Where saves and other persistent files live. `BEVY_PONG_DATA_DIR` overrides the platform default.
+pub fn data_dir() -> PathBuf {
+    if let Some(dir) = env::var_os("BEVY_PONG_DATA_DIR") {
+        return PathBuf::from(dir);
+    }
+    let base = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| Path::new(&home).join("Library/Application Support"))
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))
+    };
+    base.unwrap_or_default().join(APP_DIRECTORY)
+}
+
+pub fn exists(file_name: &str) -> bool {
+    data_dir().join(file_name).is_file()
+}
+
+pub fn read(file_name: &str) -> Option<String> {
+    fs::read_to_string(data_dir().join(file_name)).ok()
+}
+
+/// Reads a RON file from the data directory, or `None` if it is missing or unreadable.
+pub fn load<T: DeserializeOwned>(file_name: &str) -> Option<T> {
+    ron::from_str(&read(file_name)?).ok()
+}
+
+pub fn save<T: Serialize>(file_name: &str, value: &T) -> io::Result<()> {
+    let contents = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    let dir = data_dir();
+    fs::create_dir_all(&dir)?;
+    // Write to the side first so a crash can't leave a half written file behind
+    let temporary = dir.join(format!("{file_name}.tmp"));
+    fs::write(&temporary, contents)?;
+    fs::rename(temporary, dir.join(file_name))
+}
+
+/// Moves a file out of the way, so one that can't be read isn't overwritten and lost.
+pub fn backup(file_name: &str) -> io::Result<()> {
+    let dir = data_dir();
+    fs::rename(dir.join(file_name), dir.join(format!("{file_name}.bak")))
+}
+
+pub fn remove(file_name: &str) -> io::Result<()> {
+    match fs::remove_file(data_dir().join(file_name)) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,188 @@
+use std::time::Duration;
+
+use bevy::{
+    audio::{AddAudioSource, Decodable, Source},
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::ron_asset::RonAssetLoader;
+
+const SAMPLE_RATE: u32 = 44_100;
+// Noise keeps the same hiss every time it plays
+const NOISE_SEED: u64 = 0x5EED;
+
+pub struct SynthPlugin;
+
+impl Plugin for SynthPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_audio_source::<Synth>()
+            .register_asset_loader(RonAssetLoader::<Synth>::new(&["sfx.ron"]));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Waveform {
+    Square,
+    Triangle,
+    Noise,
+}
+
+/// A chiptune blip, generated as it plays. Loaded from `assets/sounds/*.sfx.ron`.
+#[derive(Asset, TypePath, Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Synth {
+    pub waveform: Waveform,
+    /// In Hz, at the start. Noise picks a new level this often.
+    pub frequency: f32,
+    /// In octaves per second, negative to slide down.
+    pub sweep: f32,
+    /// The fraction of each square wave cycle spent high, 0.5 for the fullest sound.
+    pub duty: f32,
+    /// Seconds to fade in.
+    pub attack: f32,
+    /// Seconds at full volume.
+    pub sustain: f32,
+    /// Seconds to fade out.
+    pub decay: f32,
+    /// From 0 to 1.
+    pub volume: f32,
+}
+
+impl Default for Synth {
+    fn default() -> Self {
+        Self {
+            waveform: Waveform::Square,
+            frequency: 440.,
+            sweep: 0.,
+            duty: 0.5,
+            attack: 0.,
+            sustain: 0.05,
+            decay: 0.1,
+            volume: 0.3,
+        }
+    }
+}
+
+impl Synth {
+    /// The same sound, `pitch` times higher. Unlike speeding up playback, it lasts as long.
+    pub fn with_pitch(mut self, pitch: f32) -> Self {
+        self.frequency *= pitch;
+        self
+    }
+
+    fn duration(&self) -> f32 {
+        self.attack + self.sustain + self.decay
+    }
+
+    fn envelope(&self, time: f32) -> f32 {
+        if time < self.attack {
+            time / self.attack
+        } else if time < self.attack + self.sustain {
+            1.
+        } else {
+            1. - (time - self.attack - self.sustain) / self.decay
+        }
+    }
+}
+
+impl Decodable for Synth {
+    type DecoderItem = f32;
+    type Decoder = SynthDecoder;
+
+    fn decoder(&self) -> SynthDecoder {
+        SynthDecoder {
+            length: (self.duration() * SAMPLE_RATE as f32) as u32,
+            synth: self.clone(),
+            sample: 0,
+            phase: 0.,
+            rng: fastrand::Rng::with_seed(NOISE_SEED),
+            noise: 0.,
+        }
+    }
+}
+
+/// The samples of a `Synth`, in mono.
+pub struct SynthDecoder {
+    synth: Synth,
+    length: u32,
+    sample: u32,
+    // How far through the current cycle, from 0 to 1
+    phase: f32,
+    rng: fastrand::Rng,
+    noise: f32,
+}
+
+impl Iterator for SynthDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.sample >= self.length {
+            return None;
+        }
+        let synth = &self.synth;
+        let time = self.sample as f32 / SAMPLE_RATE as f32;
+        let wave = match synth.waveform {
+            Waveform::Square if self.phase < synth.duty => 1.,
+            Waveform::Square => -1.,
+            Waveform::Triangle => 4. * (self.phase - 0.5).abs() - 1.,
+            Waveform::Noise => self.noise,
+        };
+        let value = wave * synth.envelope(time) * synth.volume;
+
+        let frequency = synth.frequency * (synth.sweep * time).exp2();
+        self.phase += frequency / SAMPLE_RATE as f32;
+        if self.phase >= 1. {
+            self.phase = self.phase.fract();
+            self.noise = self.rng.f32() * 2. - 1.;
+        }
+        self.sample += 1;
+        Some(value)
+    }
+}
+
+impl Source for SynthDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        Some((self.length - self.sample) as usize)
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        Some(Duration::from_secs_f32(self.synth.duration()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_sound_file_plays() {
+        let folder = format!("{}/assets/sounds", env!("CARGO_MANIFEST_DIR"));
+        for entry in std::fs::read_dir(&folder).unwrap() {
+            let path = entry.unwrap().path();
+            let text = std::fs::read_to_string(&path).unwrap();
+            let synth: Synth =
+                ron::from_str(&text).unwrap_or_else(|error| panic!("{}: {error}", path.display()));
+            let samples: Vec<f32> = synth.decoder().collect();
+            let expected = synth.duration() * SAMPLE_RATE as f32;
+            assert!(
+                (samples.len() as f32 - expected).abs() <= 1.,
+                "{} is the wrong length",
+                path.display()
+            );
+            assert!(
+                samples.iter().all(|sample| sample.abs() <= 1.),
+                "{} clips",
+                path.display()
+            );
+        }
+    }
+}
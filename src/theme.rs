@@ -0,0 +1,242 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::ron_asset::RonAssetLoader;
+use crate::settings::Settings;
+
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Theme>()
+            .register_asset_loader(RonAssetLoader::<Theme>::new(&["theme.ron"]))
+            .init_resource::<SelectedTheme>()
+            .init_resource::<ActiveTheme>()
+            .add_systems(Update, (activate_theme, apply_theme).chain());
+    }
+}
+
+/// Colors and font for everything drawn, loaded from `assets/themes/*.theme.ron`.
+#[derive(Asset, TypePath, Debug, Clone, Deserialize)]
+pub struct Theme {
+    #[serde(deserialize_with = "hex_color")]
+    pub background: Color,
+    #[serde(deserialize_with = "hex_color")]
+    pub ball: Color,
+    #[serde(deserialize_with = "hex_color")]
+    pub paddle: Color,
+    #[serde(deserialize_with = "hex_color")]
+    pub wall: Color,
+    #[serde(deserialize_with = "hex_color")]
+    pub goal: Color,
+    #[serde(deserialize_with = "hex_color")]
+    pub center_line: Color,
+    #[serde(deserialize_with = "hex_color")]
+    pub score: Color,
+    #[serde(deserialize_with = "hex_color")]
+    pub text: Color,
+    /// Behind the menu pages.
+    #[serde(deserialize_with = "hex_color")]
+    pub panel: Color,
+    #[serde(deserialize_with = "hex_color")]
+    pub border: Color,
+    #[serde(deserialize_with = "hex_color")]
+    pub button: Color,
+    #[serde(deserialize_with = "hex_color")]
+    pub disabled_button: Color,
+    #[serde(deserialize_with = "hex_color")]
+    pub disabled_text: Color,
+    /// Dims the arena behind the attract mode title.
+    #[serde(deserialize_with = "hex_color")]
+    pub overlay: Color,
+    #[serde(deserialize_with = "hex_color")]
+    pub toast: Color,
+    /// Around the arena's shapes, when high-contrast outlines are on.
+    #[serde(deserialize_with = "hex_color")]
+    pub outline: Color,
+    /// What the ball's trail turns as a rally goes on.
+    #[serde(deserialize_with = "hex_color")]
+    pub heat: Color,
+    /// Highlights, like the title of an achievement toast.
+    #[serde(deserialize_with = "hex_color")]
+    pub accent: Color,
+    /// Relative to the assets folder.
+    pub font_path: String,
+}
+
+impl Theme {
+    // Compiled in, so there is something to draw with before the selected theme loads
+    fn classic() -> Self {
+        ron::from_str(include_str!("../assets/themes/classic.theme.ron"))
+            .expect("the classic theme should be valid")
+    }
+
+    pub fn color(&self, themed: Themed) -> Color {
+        match themed {
+            Themed::Ball => self.ball,
+            Themed::Paddle => self.paddle,
+            Themed::Wall => self.wall,
+            Themed::Goal => self.goal,
+            Themed::CenterLine => self.center_line,
+            Themed::Score => self.score,
+            Themed::Outline => self.outline,
+        }
+    }
+}
+
+// Colors are written "#RRGGBB" or "#RRGGBBAA"
+fn hex_color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    let hex = String::deserialize(deserializer)?;
+    Color::hex(&hex).map_err(|error| serde::de::Error::custom(format!("{hex}: {error}")))
+}
+
+// Replaces the selected theme while the colorblind palette is on
+const COLORBLIND_THEME: &str = "themes/colorblind.theme.ron";
+
+/// The themes that ship with the game.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThemeName {
+    #[default]
+    Classic,
+    Neon,
+    HighContrast,
+    GameBoy,
+}
+
+impl ThemeName {
+    pub const ALL: [ThemeName; 4] = [
+        ThemeName::Classic,
+        ThemeName::Neon,
+        ThemeName::HighContrast,
+        ThemeName::GameBoy,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            ThemeName::Classic => "theme.classic",
+            ThemeName::Neon => "theme.neon",
+            ThemeName::HighContrast => "theme.high_contrast",
+            ThemeName::GameBoy => "theme.game_boy",
+        }
+    }
+
+    fn path(&self) -> &'static str {
+        match self {
+            ThemeName::Classic => "themes/classic.theme.ron",
+            ThemeName::Neon => "themes/neon.theme.ron",
+            ThemeName::HighContrast => "themes/high_contrast.theme.ron",
+            ThemeName::GameBoy => "themes/game_boy.theme.ron",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|theme| theme == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// Which of the theme's colors an entity is drawn in. Sprites, color materials and text
+/// with this component are recolored whenever the theme changes.
+#[derive(Component, Debug, Clone, Copy)]
+pub enum Themed {
+    Ball,
+    Paddle,
+    Wall,
+    Goal,
+    CenterLine,
+    Score,
+    Outline,
+}
+
+/// The theme everything is drawn with right now.
+#[derive(Resource, Deref)]
+pub struct ActiveTheme {
+    #[deref]
+    theme: Theme,
+    pub font: Handle<Font>,
+}
+
+impl FromWorld for ActiveTheme {
+    fn from_world(world: &mut World) -> Self {
+        let theme = Theme::classic();
+        let font = world.resource::<AssetServer>().load(&theme.font_path);
+        Self { theme, font }
+    }
+}
+
+// The theme picked in the settings, which may still be loading
+#[derive(Resource, Default)]
+struct SelectedTheme(Handle<Theme>);
+
+// Switches to the selected theme once it has loaded, and again whenever its file is edited
+fn activate_theme(
+    settings: Res<Settings>,
+    asset_server: Res<AssetServer>,
+    themes: Res<Assets<Theme>>,
+    mut asset_events: EventReader<AssetEvent<Theme>>,
+    mut selected: ResMut<SelectedTheme>,
+    mut active: ResMut<ActiveTheme>,
+) {
+    let mut changed = false;
+    if settings.is_changed() {
+        let path = match settings.colorblind {
+            true => COLORBLIND_THEME,
+            false => settings.theme.path(),
+        };
+        let handle = asset_server.load(path);
+        if handle != selected.0 {
+            selected.0 = handle;
+            changed = true;
+        }
+    }
+    for event in asset_events.read() {
+        changed |= event.is_loaded_with_dependencies(&selected.0) || event.is_modified(&selected.0);
+    }
+    if !changed {
+        return;
+    }
+    // Not loaded yet, its loaded event comes later
+    let Some(theme) = themes.get(&selected.0) else {
+        return;
+    };
+    active.font = asset_server.load(&theme.font_path);
+    active.theme = theme.clone();
+}
+
+// Recolors new themed entities, or all of them when the theme changes
+fn apply_theme(
+    theme: Res<ActiveTheme>,
+    mut clear_color: ResMut<ClearColor>,
+    mut sprite_query: Query<(Ref<Themed>, &mut Sprite)>,
+    material_query: Query<(Ref<Themed>, &Handle<ColorMaterial>)>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut text_query: Query<(Ref<Themed>, &mut Text)>,
+) {
+    let all = theme.is_changed();
+    if all {
+        clear_color.0 = theme.background;
+    }
+    for (themed, mut sprite) in &mut sprite_query {
+        if all || themed.is_added() {
+            sprite.color = theme.color(*themed);
+        }
+    }
+    for (themed, handle) in &material_query {
+        if all || themed.is_added() {
+            if let Some(material) = materials.get_mut(handle) {
+                material.color = theme.color(*themed);
+            }
+        }
+    }
+    for (themed, mut text) in &mut text_query {
+        if all || themed.is_added() {
+            for section in &mut text.sections {
+                section.style.color = theme.color(*themed);
+                section.style.font = theme.font.clone();
+            }
+        }
+    }
+}
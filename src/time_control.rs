@@ -0,0 +1,220 @@
+use std::collections::VecDeque;
+
+use bevy::{app::FixedMain, prelude::*};
+
+use crate::camera::DevPause;
+use crate::save::MatchSnapshot;
+use crate::schedule::{GameState, InGameSet};
+use crate::theme::ActiveTheme;
+
+// How far back the match can be rewound
+const REWIND_SECONDS: f32 = 10.;
+const MIN_SPEED: f32 = 1. / 16.;
+const MAX_SPEED: f32 = 4.;
+const FONT_SIZE: f32 = 18.;
+
+/// Developer controls for the flow of time, only built with the `dev-tools` feature.
+/// `-` and `=` halve and double the speed, F6 pauses, F7 steps one fixed tick while
+/// paused, and holding F8 rewinds.
+pub struct TimeControlPlugin;
+
+impl Plugin for TimeControlPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RewindBuffer>()
+            .add_systems(Startup, spawn_status_text)
+            .add_systems(OnEnter(GameState::Reset), clear_rewind_buffer)
+            // The menu's buttons run on fixed ticks, so it mustn't be left paused
+            .add_systems(OnExit(GameState::Playing), resume_time)
+            .add_systems(
+                FixedUpdate,
+                record_snapshot
+                    .after(InGameSet::CollisionDetection)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (
+                    (change_speed, toggle_pause, rewind, step_tick)
+                        .chain()
+                        .run_if(in_state(GameState::Playing)),
+                    update_status_text,
+                )
+                    .chain(),
+            );
+    }
+}
+
+// Snapshots of the last few seconds of the match, oldest first
+#[derive(Resource, Default)]
+struct RewindBuffer {
+    snapshots: VecDeque<MatchSnapshot>,
+    rewinding: bool,
+    // Whether to stay paused once rewinding stops
+    was_paused: bool,
+}
+
+#[derive(Component)]
+struct StatusText;
+
+fn spawn_status_text(mut commands: Commands) {
+    commands.spawn((
+        TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(8.),
+                right: Val::Px(8.),
+                padding: UiRect::axes(Val::Px(8.), Val::Px(4.)),
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            z_index: ZIndex::Global(i32::MAX),
+            ..default()
+        },
+        StatusText,
+    ));
+}
+
+fn record_snapshot(world: &mut World) {
+    let timestep = world.resource::<Time<Fixed>>().timestep().as_secs_f32();
+    let capacity = (REWIND_SECONDS / timestep) as usize;
+    let snapshot = MatchSnapshot::capture(world);
+
+    let mut buffer = world.resource_mut::<RewindBuffer>();
+    if buffer.snapshots.len() >= capacity {
+        buffer.snapshots.pop_front();
+    }
+    buffer.snapshots.push_back(snapshot);
+}
+
+fn clear_rewind_buffer(mut buffer: ResMut<RewindBuffer>) {
+    buffer.snapshots.clear();
+}
+
+fn resume_time(
+    mut time: ResMut<Time<Virtual>>,
+    mut buffer: ResMut<RewindBuffer>,
+    mut dev_pause: ResMut<DevPause>,
+) {
+    time.unpause();
+    buffer.rewinding = false;
+    dev_pause.0 = false;
+}
+
+fn change_speed(input: Res<ButtonInput<KeyCode>>, mut time: ResMut<Time<Virtual>>) {
+    let speed = time.relative_speed();
+    if input.just_pressed(KeyCode::Minus) {
+        time.set_relative_speed((speed / 2.).max(MIN_SPEED));
+    }
+    if input.just_pressed(KeyCode::Equal) {
+        time.set_relative_speed((speed * 2.).min(MAX_SPEED));
+    }
+}
+
+// Goes by `DevPause` rather than the clock, which a hit-stop may have paused for a moment
+fn toggle_pause(
+    input: Res<ButtonInput<KeyCode>>,
+    mut time: ResMut<Time<Virtual>>,
+    buffer: Res<RewindBuffer>,
+    mut dev_pause: ResMut<DevPause>,
+) {
+    if !input.just_pressed(KeyCode::F6) || buffer.rewinding {
+        return;
+    }
+    dev_pause.0 = !dev_pause.0;
+    match dev_pause.0 {
+        true => time.pause(),
+        false => time.unpause(),
+    }
+}
+
+// Steps back one fixed tick each frame for as long as F8 is held, paused meanwhile
+fn rewind(world: &mut World) {
+    let held = world
+        .resource::<ButtonInput<KeyCode>>()
+        .pressed(KeyCode::F8);
+    let rewinding = world.resource::<RewindBuffer>().rewinding;
+
+    match (held, rewinding) {
+        (true, false) => {
+            let was_paused = world.resource::<DevPause>().0;
+            world.resource_mut::<DevPause>().0 = true;
+            world.resource_mut::<Time<Virtual>>().pause();
+            let mut buffer = world.resource_mut::<RewindBuffer>();
+            buffer.rewinding = true;
+            buffer.was_paused = was_paused;
+        }
+        (false, true) => {
+            let mut buffer = world.resource_mut::<RewindBuffer>();
+            buffer.rewinding = false;
+            let was_paused = buffer.was_paused;
+            world.resource_mut::<DevPause>().0 = was_paused;
+            if !was_paused {
+                world.resource_mut::<Time<Virtual>>().unpause();
+            }
+            return;
+        }
+        (false, false) => return,
+        (true, true) => {}
+    }
+
+    // The newest snapshot is where the match already is, keep it to resume from
+    let mut buffer = world.resource_mut::<RewindBuffer>();
+    if buffer.snapshots.len() > 1 {
+        buffer.snapshots.pop_back();
+    }
+    if let Some(snapshot) = buffer.snapshots.back().cloned() {
+        snapshot.apply(world);
+    }
+}
+
+// Runs exactly one fixed tick while paused, the same way the fixed loop would
+fn step_tick(world: &mut World) {
+    let paused = world.resource::<DevPause>().0;
+    let stepping = world
+        .resource::<ButtonInput<KeyCode>>()
+        .just_pressed(KeyCode::F7);
+    if !paused || !stepping || world.resource::<RewindBuffer>().rewinding {
+        return;
+    }
+    *world.resource_mut::<Time>() = world.resource::<Time<Fixed>>().as_generic();
+    world.run_schedule(FixedMain);
+    *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
+}
+
+fn update_status_text(
+    time: Res<Time<Virtual>>,
+    dev_pause: Res<DevPause>,
+    fixed_time: Res<Time<Fixed>>,
+    buffer: Res<RewindBuffer>,
+    theme: Res<ActiveTheme>,
+    mut text_query: Query<(&mut Text, &mut BackgroundColor, &mut Visibility), With<StatusText>>,
+) {
+    let speed = time.relative_speed();
+    let mut status = Vec::new();
+    if speed != 1. {
+        status.push(format!("{speed}x"));
+    }
+    if buffer.rewinding {
+        let seconds = buffer.snapshots.len() as f32 * fixed_time.timestep().as_secs_f32();
+        status.push(format!("Rewind ({seconds:.1}s left)"));
+    } else if dev_pause.0 {
+        status.push("Paused, F7 to step".to_string());
+    }
+
+    for (mut text, mut background, mut visibility) in &mut text_query {
+        if status.is_empty() {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+        *text = Text::from_section(
+            status.join("  "),
+            TextStyle {
+                font: theme.font.clone(),
+                font_size: FONT_SIZE,
+                color: theme.text,
+            },
+        );
+        *background = theme.toast.into();
+        *visibility = Visibility::Inherited;
+    }
+}
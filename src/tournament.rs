@@ -0,0 +1,430 @@
+use std::{cmp::Ordering, collections::BTreeMap, fmt::Write};
+
+use bevy::{ecs::event::ManualEventReader, prelude::*};
+use serde::Serialize;
+
+use crate::controller::{Controller, ControllerRegistry, PaddleController};
+use crate::events::{PaddleHit, PointScored};
+use crate::headless::headless_app;
+use crate::paddle::Paddle;
+use crate::{MatchRules, ServeDirection, Side};
+
+// Relative hit offsets, where 0 is the center of the paddle and 0.5 its end
+const CENTER_ZONE: f32 = 0.15;
+const OFF_CENTER_ZONE: f32 = 0.35;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Format {
+    RoundRobin,
+    Bracket,
+}
+
+/// Where on the paddle the last shot of a point was played from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HitZone {
+    /// The point was won straight off the serve.
+    Serve,
+    Center,
+    OffCenter,
+    Edge,
+}
+
+impl HitZone {
+    const ALL: [HitZone; 4] = [
+        HitZone::Serve,
+        HitZone::Center,
+        HitZone::OffCenter,
+        HitZone::Edge,
+    ];
+
+    fn from_offset(offset: f32) -> Self {
+        match offset.abs() {
+            offset if offset < CENTER_ZONE => HitZone::Center,
+            offset if offset < OFF_CENTER_ZONE => HitZone::OffCenter,
+            _ => HitZone::Edge,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            HitZone::Serve => "serve",
+            HitZone::Center => "center",
+            HitZone::OffCenter => "off_center",
+            HitZone::Edge => "edge",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TournamentConfig {
+    pub format: Format,
+    pub entrants: Vec<String>,
+    pub matches_per_pairing: u32,
+    pub seed: u64,
+    pub points_to_win: u32,
+    /// Matches still undecided after this many fixed ticks end early.
+    pub max_ticks: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Point {
+    pub scorer: String,
+    pub hits: u32,
+    pub zone: HitZone,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchResult {
+    pub seed: u64,
+    pub left: String,
+    pub right: String,
+    pub left_score: u32,
+    pub right_score: u32,
+    /// `None` for a draw, when the match ran out of time level.
+    pub winner: Option<String>,
+    pub ticks: u32,
+    pub points: Vec<Point>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Standing {
+    pub name: String,
+    pub matches: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub win_rate: f32,
+    pub points_for: u32,
+    pub points_against: u32,
+    pub average_rally_length: f32,
+    pub points_conceded_by_zone: BTreeMap<&'static str, u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TournamentReport {
+    pub format: Format,
+    pub seed: u64,
+    pub matches_per_pairing: u32,
+    pub points_to_win: u32,
+    /// Winner of the final, for bracket tournaments.
+    pub champion: Option<String>,
+    pub standings: Vec<Standing>,
+    pub matches: Vec<MatchResult>,
+}
+
+impl TournamentReport {
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// The standings table, one row per controller.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "name,matches,wins,losses,draws,win_rate,points_for,points_against,average_rally_length",
+        );
+        for zone in HitZone::ALL {
+            let _ = write!(csv, ",conceded_{}", zone.name());
+        }
+        csv.push('\n');
+
+        for standing in &self.standings {
+            let _ = write!(
+                csv,
+                "{},{},{},{},{},{:.3},{},{},{:.2}",
+                standing.name,
+                standing.matches,
+                standing.wins,
+                standing.losses,
+                standing.draws,
+                standing.win_rate,
+                standing.points_for,
+                standing.points_against,
+                standing.average_rally_length,
+            );
+            for zone in HitZone::ALL {
+                let conceded = standing.points_conceded_by_zone.get(zone.name());
+                let _ = write!(csv, ",{}", conceded.copied().unwrap_or(0));
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+pub fn run_tournament(
+    registry: &ControllerRegistry,
+    config: &TournamentConfig,
+) -> TournamentReport {
+    let mut runner = Runner {
+        registry,
+        config,
+        next_seed: config.seed,
+        matches: Vec::new(),
+    };
+
+    let champion = match config.format {
+        Format::RoundRobin => {
+            for (index, left) in config.entrants.iter().enumerate() {
+                for right in &config.entrants[index + 1..] {
+                    runner.play_series(left, right);
+                }
+            }
+            None
+        }
+        Format::Bracket => {
+            let mut round = config.entrants.clone();
+            while round.len() > 1 {
+                // An odd entrant out gets a bye into the next round
+                round = round
+                    .chunks(2)
+                    .map(|pair| match pair {
+                        [left, right] => runner.play_series(left, right),
+                        [bye] => bye.clone(),
+                        _ => unreachable!(),
+                    })
+                    .collect();
+            }
+            round.pop()
+        }
+    };
+
+    TournamentReport {
+        format: config.format,
+        seed: config.seed,
+        matches_per_pairing: config.matches_per_pairing,
+        points_to_win: config.points_to_win,
+        champion,
+        standings: standings(&config.entrants, &runner.matches),
+        matches: runner.matches,
+    }
+}
+
+struct Runner<'a> {
+    registry: &'a ControllerRegistry,
+    config: &'a TournamentConfig,
+    next_seed: u64,
+    matches: Vec<MatchResult>,
+}
+
+impl Runner<'_> {
+    // Plays every match between two entrants, swapping ends each match, and returns the series winner
+    fn play_series(&mut self, first: &str, second: &str) -> String {
+        let mut balance = (0i64, 0i64);
+        for index in 0..self.config.matches_per_pairing {
+            let (left, right) = match index % 2 {
+                0 => (first, second),
+                _ => (second, first),
+            };
+            let result = self.play_match(left, right);
+
+            let first_score = match left == first {
+                true => (result.left_score, result.right_score),
+                false => (result.right_score, result.left_score),
+            };
+            match result.winner.as_deref() {
+                Some(winner) if winner == first => balance.0 += 1,
+                Some(_) => balance.0 -= 1,
+                None => {}
+            }
+            balance.1 += first_score.0 as i64 - first_score.1 as i64;
+            self.matches.push(result);
+        }
+        // Ties go to the higher seed
+        match balance >= (0, 0) {
+            true => first.to_string(),
+            false => second.to_string(),
+        }
+    }
+
+    fn play_match(&mut self, left: &str, right: &str) -> MatchResult {
+        let seed = self.next_seed;
+        self.next_seed += 1;
+        let factory = |name: &str| {
+            self.registry
+                .get(name)
+                .unwrap_or_else(|| panic!("No controller registered as {name}"))
+        };
+        // Give each side its own stream of randomness
+        let outcome = run_match(
+            factory(left)(seed.wrapping_mul(2)),
+            factory(right)(seed.wrapping_mul(2) + 1),
+            seed,
+            self.config.points_to_win,
+            self.config.max_ticks,
+        );
+
+        let name = |side: Side| match side {
+            Side::Left => left.to_string(),
+            Side::Right => right.to_string(),
+        };
+        MatchResult {
+            seed,
+            left: name(Side::Left),
+            right: name(Side::Right),
+            left_score: outcome.left_score,
+            right_score: outcome.right_score,
+            winner: outcome.winner().map(name),
+            ticks: outcome.ticks,
+            points: outcome
+                .points
+                .iter()
+                .map(|point| Point {
+                    scorer: name(point.scorer),
+                    hits: point.hits,
+                    zone: point.zone,
+                })
+                .collect(),
+        }
+    }
+}
+
+pub struct PointOutcome {
+    pub scorer: Side,
+    pub hits: u32,
+    pub zone: HitZone,
+}
+
+pub struct MatchOutcome {
+    pub left_score: u32,
+    pub right_score: u32,
+    pub ticks: u32,
+    pub points: Vec<PointOutcome>,
+}
+
+impl MatchOutcome {
+    pub fn winner(&self) -> Option<Side> {
+        match self.left_score.cmp(&self.right_score) {
+            Ordering::Greater => Some(Side::Left),
+            Ordering::Less => Some(Side::Right),
+            Ordering::Equal => None,
+        }
+    }
+}
+
+/// Plays a single headless match until a side reaches `points_to_win` or `max_ticks` have passed.
+pub fn run_match(
+    left: Box<dyn PaddleController>,
+    right: Box<dyn PaddleController>,
+    seed: u64,
+    points_to_win: u32,
+    max_ticks: u32,
+) -> MatchOutcome {
+    let mut app = headless_app();
+    // So the simulation ends the match where the tournament does
+    app.insert_resource(MatchRules { points_to_win });
+    let mut controllers = [Some(left), Some(right)];
+    let mut paddle_query = app.world.query::<(Entity, &Paddle)>();
+    let paddles: Vec<(Entity, Side)> = paddle_query
+        .iter(&app.world)
+        .map(|(entity, paddle)| (entity, paddle.side))
+        .collect();
+    for (entity, side) in paddles {
+        let controller = match side {
+            Side::Left => controllers[0].take(),
+            Side::Right => controllers[1].take(),
+        };
+        if let Some(controller) = controller {
+            app.world.entity_mut(entity).insert(Controller(controller));
+        }
+    }
+    if seed % 2 == 1 {
+        *app.world.resource_mut::<ServeDirection>() = ServeDirection::Left;
+    }
+
+    let mut paddle_hit_reader = ManualEventReader::<PaddleHit>::default();
+    let mut point_reader = ManualEventReader::<PointScored>::default();
+
+    let mut outcome = MatchOutcome {
+        left_score: 0,
+        right_score: 0,
+        ticks: 0,
+        points: Vec::new(),
+    };
+    let mut hits = 0;
+    let mut zone = HitZone::Serve;
+
+    while outcome.ticks < max_ticks {
+        app.update();
+        outcome.ticks += 1;
+
+        let paddle_hits = app.world.resource::<Events<PaddleHit>>();
+        for hit in paddle_hit_reader.read(paddle_hits) {
+            hits += 1;
+            zone = HitZone::from_offset(hit.hit_offset);
+        }
+
+        let points = app.world.resource::<Events<PointScored>>();
+        for point in point_reader.read(points) {
+            outcome.points.push(PointOutcome {
+                scorer: point.scorer,
+                hits,
+                zone,
+            });
+            match point.scorer {
+                Side::Left => outcome.left_score = point.new_score,
+                Side::Right => outcome.right_score = point.new_score,
+            }
+            hits = 0;
+            zone = HitZone::Serve;
+        }
+        if outcome.left_score >= points_to_win || outcome.right_score >= points_to_win {
+            break;
+        }
+    }
+    outcome
+}
+
+fn standings(entrants: &[String], matches: &[MatchResult]) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = entrants
+        .iter()
+        .map(|name| Standing {
+            name: name.clone(),
+            points_conceded_by_zone: HitZone::ALL.iter().map(|zone| (zone.name(), 0)).collect(),
+            ..default()
+        })
+        .collect();
+
+    for standing in &mut standings {
+        let mut rallies = 0;
+        let mut total_hits = 0;
+        for result in matches {
+            let (scored, conceded) = if result.left == standing.name {
+                (result.left_score, result.right_score)
+            } else if result.right == standing.name {
+                (result.right_score, result.left_score)
+            } else {
+                continue;
+            };
+
+            standing.matches += 1;
+            standing.points_for += scored;
+            standing.points_against += conceded;
+            match &result.winner {
+                Some(winner) if *winner == standing.name => standing.wins += 1,
+                Some(_) => standing.losses += 1,
+                None => standing.draws += 1,
+            }
+            for point in &result.points {
+                rallies += 1;
+                total_hits += point.hits;
+                if point.scorer != standing.name {
+                    *standing
+                        .points_conceded_by_zone
+                        .entry(point.zone.name())
+                        .or_default() += 1;
+                }
+            }
+        }
+        if standing.matches > 0 {
+            standing.win_rate = standing.wins as f32 / standing.matches as f32;
+        }
+        if rallies > 0 {
+            standing.average_rally_length = total_hits as f32 / rallies as f32;
+        }
+    }
+
+    standings.sort_by(|a, b| b.win_rate.total_cmp(&a.win_rate));
+    standings
+}
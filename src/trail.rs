@@ -0,0 +1,168 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    prelude::*,
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+};
+
+use crate::ball::{self, reset_ball, reset_ball_goal, Ball};
+use crate::interpolation::Position;
+use crate::schedule::{GameState, InGameSet};
+use crate::settings::Settings;
+use crate::theme::ActiveTheme;
+use crate::wall::GoalEvent;
+use crate::Rally;
+
+/// Longest trail the settings can ask for, in fixed ticks.
+pub const MAX_LENGTH: usize = 32;
+// Dots drawn between two samples
+const SUBDIVISIONS: usize = 3;
+const Z: f32 = -0.5;
+
+// Rally hits at which the trail is fully the heat color
+const HOT_HITS: u32 = 12;
+// In ticks of the ball's travel, a jump further than this in one tick is a teleport
+const TELEPORT_STEPS: f32 = 4.;
+const MAX_ALPHA: f32 = 0.5;
+const TAIL_SCALE: f32 = 0.3;
+
+pub struct TrailPlugin;
+
+impl Plugin for TrailPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TrailSamples>()
+            .add_systems(Startup, spawn_trail)
+            .add_systems(
+                FixedUpdate,
+                (
+                    clear_trail_goal
+                        .after(reset_ball_goal)
+                        .in_set(InGameSet::ResetEntities),
+                    sample_trail
+                        .after(InGameSet::CollisionDetection)
+                        .run_if(in_state(GameState::Playing).or_else(in_state(GameState::Attract))),
+                ),
+            )
+            .add_systems(OnEnter(GameState::Reset), clear_trail.after(reset_ball))
+            .add_systems(Update, draw_trail);
+    }
+}
+
+struct Sample {
+    position: Vec2,
+    // From 0 at the serve to 1 once the rally is long
+    heat: f32,
+}
+
+// Newest first
+#[derive(Resource, Default)]
+struct TrailSamples(VecDeque<Sample>);
+
+#[derive(Component)]
+struct TrailDot(usize);
+
+fn spawn_trail(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let mesh = Mesh2dHandle(meshes.add(Circle::new(ball::RADIUS)));
+    for index in 0..MAX_LENGTH * SUBDIVISIONS {
+        commands.spawn((
+            MaterialMesh2dBundle {
+                mesh: mesh.clone(),
+                material: materials.add(ColorMaterial::default()),
+                transform: Transform::from_xyz(0., 0., Z),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            TrailDot(index),
+        ));
+    }
+}
+
+fn sample_trail(
+    mut samples: ResMut<TrailSamples>,
+    ball_query: Query<&Position, With<Ball>>,
+    rally: Res<Rally>,
+    time: Res<Time>,
+) {
+    let Ok(position) = ball_query.get_single() else {
+        return;
+    };
+    let position = position.truncate();
+    // Other teleports, such as loading a save, show up as a jump no ball could make in one tick
+    let max_step = TELEPORT_STEPS * ball::SPEED * time.delta_seconds();
+    if let Some(previous) = samples.0.front() {
+        if previous.position.distance(position) > max_step {
+            samples.0.clear();
+        }
+    }
+    samples.0.push_front(Sample {
+        position,
+        heat: (rally.hits as f32 / HOT_HITS as f32).min(1.),
+    });
+    samples.0.truncate(MAX_LENGTH);
+}
+
+fn clear_trail(mut samples: ResMut<TrailSamples>) {
+    samples.0.clear();
+}
+
+fn clear_trail_goal(mut samples: ResMut<TrailSamples>, mut goal_events: EventReader<GoalEvent>) {
+    for _ in goal_events.read() {
+        samples.0.clear();
+    }
+}
+
+fn draw_trail(
+    samples: Res<TrailSamples>,
+    settings: Res<Settings>,
+    theme: Res<ActiveTheme>,
+    fixed_time: Res<Time<Fixed>>,
+    mut dot_query: Query<(
+        &TrailDot,
+        &mut Transform,
+        &mut Visibility,
+        &Handle<ColorMaterial>,
+    )>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let length = match settings.trail {
+        true => samples.0.len().min(settings.trail_length),
+        false => 0,
+    };
+    let dots = length.saturating_sub(1) * SUBDIVISIONS;
+    // The drawn ball is part way from the previous sample to the newest, so the trail starts
+    // where it is drawn rather than ahead of it
+    let lag = 1. - fixed_time.overstep_fraction();
+
+    for (dot, mut transform, mut visibility, material) in &mut dot_query {
+        // In ticks behind the newest sample
+        let ticks = dot.0 as f32 / SUBDIVISIONS as f32 + lag;
+        let segment = ticks as usize;
+        if dot.0 >= dots || segment + 1 >= length {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+        *visibility = Visibility::Inherited;
+
+        // Interpolate between the two samples either side of the dot
+        let fraction = ticks.fract();
+        let (newer, older) = (&samples.0[segment], &samples.0[segment + 1]);
+        let position = newer.position.lerp(older.position, fraction);
+        let heat = newer.heat + (older.heat - newer.heat) * fraction;
+
+        let age = dot.0 as f32 / dots as f32;
+        transform.translation = position.extend(Z);
+        transform.scale = Vec3::splat(1. - age * (1. - TAIL_SCALE));
+
+        let color = theme
+            .ball
+            .rgba_to_vec4()
+            .lerp(theme.heat.rgba_to_vec4(), heat);
+        if let Some(material) = materials.get_mut(material) {
+            material.color = Color::rgba_from_array(color).with_a(MAX_ALPHA * (1. - age));
+        }
+    }
+}
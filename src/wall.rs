@@ -1,11 +1,11 @@
 use bevy::prelude::*;
 
-use crate::{Collider, Side, HEIGHT, WIDTH};
+use crate::{
+    accessibility::outline_sprite, interpolation::Position, theme::Themed, Collider, Side, HEIGHT,
+    WIDTH,
+};
 
-const GOAL_COLOR: Color = Color::DARK_GRAY;
-const COLOR: Color = Color::WHITE;
 const THICKNESS: f32 = 20.;
-const CENTER_SECTION_COLOR: Color = Color::DARK_GRAY;
 const CENTER_SECTION_HEIGHT: f32 = 40.;
 const CENTER_GAP_HEIGHT: f32 = 20.;
 
@@ -20,44 +20,80 @@ impl Plugin for WallPlugin {
 
 fn spawn_walls(mut commands: Commands) {
     // Walls
-    commands.spawn(WallBundle::new(WallLocation::Top));
-    commands.spawn(WallBundle::new(WallLocation::Bottom));
-    commands.spawn(GoalBundle::new(WallLocation::Right, Side::Right));
-    commands.spawn(GoalBundle::new(WallLocation::Left, Side::Left));
+    for location in [WallLocation::Top, WallLocation::Bottom] {
+        let size = location.size();
+        commands
+            .spawn(WallBundle::new(location))
+            .with_children(|parent| {
+                parent.spawn(outline_sprite(size));
+            });
+    }
+    for (location, side) in [
+        (WallLocation::Right, Side::Right),
+        (WallLocation::Left, Side::Left),
+    ] {
+        let size = location.size();
+        commands
+            .spawn(GoalBundle::new(location, side))
+            .with_children(|parent| {
+                parent.spawn(outline_sprite(size));
+            });
+    }
 }
 
 // Worked out manually, not an ideal solution
 fn spawn_center_line(mut commands: Commands) {
     let interval = CENTER_SECTION_HEIGHT + CENTER_GAP_HEIGHT;
 
+    let size = Vec2 {
+        x: THICKNESS,
+        y: CENTER_SECTION_HEIGHT,
+    };
     let center_sprite = Sprite {
-        color: CENTER_SECTION_COLOR,
-        custom_size: Some(Vec2 {
-            x: THICKNESS,
-            y: CENTER_SECTION_HEIGHT,
-        }),
+        custom_size: Some(size),
         ..default()
     };
 
     // Center Block
-    commands.spawn(SpriteBundle {
-        sprite: center_sprite.clone(),
-        transform: Transform::from_translation(Vec3::new(0., 0., -1.)),
-        ..default()
-    });
+    commands
+        .spawn((
+            SpriteBundle {
+                sprite: center_sprite.clone(),
+                transform: Transform::from_translation(Vec3::new(0., 0., -1.)),
+                ..default()
+            },
+            Themed::CenterLine,
+        ))
+        .with_children(|parent| {
+            parent.spawn(outline_sprite(size));
+        });
 
     for dy in 1..4 {
         let absolute_y = interval * (dy as f32);
-        commands.spawn(SpriteBundle {
-            sprite: center_sprite.clone(),
-            transform: Transform::from_translation(Vec3::new(0., absolute_y, -1.)),
-            ..default()
-        });
-        commands.spawn(SpriteBundle {
-            sprite: center_sprite.clone(),
-            transform: Transform::from_translation(Vec3::new(0., -absolute_y, -1.)),
-            ..default()
-        });
+        commands
+            .spawn((
+                SpriteBundle {
+                    sprite: center_sprite.clone(),
+                    transform: Transform::from_translation(Vec3::new(0., absolute_y, -1.)),
+                    ..default()
+                },
+                Themed::CenterLine,
+            ))
+            .with_children(|parent| {
+                parent.spawn(outline_sprite(size));
+            });
+        commands
+            .spawn((
+                SpriteBundle {
+                    sprite: center_sprite.clone(),
+                    transform: Transform::from_translation(Vec3::new(0., -absolute_y, -1.)),
+                    ..default()
+                },
+                Themed::CenterLine,
+            ))
+            .with_children(|parent| {
+                parent.spawn(outline_sprite(size));
+            });
     }
 }
 
@@ -66,13 +102,15 @@ pub struct Goal {
     pub side: Side,
 }
 
-#[derive(Event, Default)]
+#[derive(Event, Debug, Default, Clone, Copy)]
 pub struct GoalEvent(pub Side);
 
 #[derive(Bundle)]
 struct WallBundle {
     sprite_bundle: SpriteBundle,
+    position: Position,
     collider: Collider,
+    themed: Themed,
 }
 
 impl WallBundle {
@@ -82,14 +120,15 @@ impl WallBundle {
         WallBundle {
             sprite_bundle: SpriteBundle {
                 sprite: Sprite {
-                    color: COLOR,
                     custom_size: Some(size),
                     ..default()
                 },
                 transform: Transform::from_translation(center),
                 ..default()
             },
+            position: Position::new(center),
             collider: Collider { bounding_box: size },
+            themed: Themed::Wall,
         }
     }
 }
@@ -97,8 +136,10 @@ impl WallBundle {
 #[derive(Bundle)]
 struct GoalBundle {
     sprite_bundle: SpriteBundle,
+    position: Position,
     collider: Collider,
     goal: Goal,
+    themed: Themed,
 }
 
 impl GoalBundle {
@@ -108,15 +149,16 @@ impl GoalBundle {
         GoalBundle {
             sprite_bundle: SpriteBundle {
                 sprite: Sprite {
-                    color: GOAL_COLOR,
                     custom_size: Some(size),
                     ..default()
                 },
                 transform: Transform::from_translation(center),
                 ..default()
             },
+            position: Position::new(center),
             collider: Collider { bounding_box: size },
             goal: Goal { side },
+            themed: Themed::Goal,
         }
     }
 }
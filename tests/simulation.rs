@@ -0,0 +1,193 @@
+//! The match rules, checked by driving the headless simulation a tick at a time.
+
+use bevy::prelude::*;
+use bevy_pong::events::{MatchEnded, PaddleHit, PointScored, ServeStarted, WallBounce};
+use bevy_pong::harness::Harness;
+use bevy_pong::paddle;
+use bevy_pong::schedule::GameState;
+use bevy_pong::wall::GoalEvent;
+use bevy_pong::{Side, HEIGHT, WIDTH};
+
+// Comfortably longer than the wait before a serve
+const SERVE_TICKS: u32 = 200;
+// Long enough to cross the arena from the center
+const CROSSING_TICKS: u32 = 200;
+
+fn wait_for_serve(harness: &mut Harness) -> Side {
+    let mut serves = Vec::new();
+    harness.tick_until(SERVE_TICKS, |harness| {
+        serves.extend(harness.events::<ServeStarted>());
+        !serves.is_empty()
+    });
+    assert_eq!(serves.len(), 1, "served more than once");
+    serves[0].towards
+}
+
+// Sends the ball into `side`'s goal, well out of reach of that side's paddle
+fn score_against(harness: &mut Harness, side: Side) -> GoalEvent {
+    let direction = match side {
+        Side::Left => -1.,
+        Side::Right => 1.,
+    };
+    harness.place_ball(
+        Vec2::new(direction * (WIDTH / 2. - 15.), HEIGHT / 2. - 30.),
+        Vec2::new(direction, 0.),
+    );
+    let mut goals = Vec::new();
+    harness.tick_until(CROSSING_TICKS, |harness| {
+        goals.extend(harness.events::<GoalEvent>());
+        !goals.is_empty()
+    });
+    assert_eq!(goals.len(), 1, "scored more than once");
+    // Goals are found after everything has moved, and counted on the next tick
+    harness.tick(1);
+    goals[0]
+}
+
+#[test]
+fn serves_alternate_after_each_point() {
+    let mut harness = Harness::new();
+    assert_eq!(wait_for_serve(&mut harness), Side::Right);
+    score_against(&mut harness, Side::Left);
+    assert_eq!(wait_for_serve(&mut harness), Side::Left);
+    score_against(&mut harness, Side::Left);
+    assert_eq!(wait_for_serve(&mut harness), Side::Right);
+}
+
+#[test]
+fn ball_waits_at_the_center_to_serve() {
+    let mut harness = Harness::new();
+    harness.tick(10);
+    assert_eq!(harness.ball_position(), Vec2::ZERO);
+    assert_eq!(harness.ball_velocity(), Vec2::ZERO);
+    assert_eq!(harness.ball_transform(), Vec2::ZERO);
+
+    wait_for_serve(&mut harness);
+    harness.tick(2);
+    let position = harness.ball_position();
+    assert!(position.x > 0.);
+    // Drawn on its way from the center, never ahead of the simulation
+    let drawn = harness.ball_transform();
+    assert!(
+        drawn.x > 0. && drawn.x <= position.x,
+        "{drawn} for {position}"
+    );
+}
+
+#[test]
+fn goals_score_for_the_opposite_side() {
+    let mut harness = Harness::new();
+    wait_for_serve(&mut harness);
+
+    assert_eq!(score_against(&mut harness, Side::Right).0, Side::Right);
+    assert_eq!(harness.score(), (1, 0));
+    let points = harness.events::<PointScored>();
+    assert_eq!(points.len(), 1);
+    assert_eq!(points[0].scorer, Side::Left);
+    assert_eq!(points[0].new_score, 1);
+    // The ball goes back to the center to be served again, without sliding there
+    assert_eq!(harness.ball_position(), Vec2::ZERO);
+    assert_eq!(harness.ball_transform(), Vec2::ZERO);
+
+    wait_for_serve(&mut harness);
+    assert_eq!(score_against(&mut harness, Side::Left).0, Side::Left);
+    assert_eq!(harness.score(), (1, 1));
+    let points = harness.events::<PointScored>();
+    assert_eq!(points[0].scorer, Side::Right);
+}
+
+#[test]
+fn ball_bounces_off_paddles_and_walls() {
+    let mut harness = Harness::new();
+    wait_for_serve(&mut harness);
+
+    // Straight at the middle of the player's paddle, which stays put
+    let paddle = harness.paddle_position(Side::Right);
+    harness.place_ball(Vec2::new(paddle.x - 100., paddle.y), Vec2::X);
+    let mut hits = Vec::new();
+    harness.tick_until(CROSSING_TICKS, |harness| {
+        hits.extend(harness.events::<PaddleHit>());
+        !hits.is_empty()
+    });
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].side, Side::Right);
+    assert!(hits[0].hit_offset.abs() < 0.1, "{}", hits[0].hit_offset);
+    assert!(harness.ball_velocity().x < 0.);
+
+    // Up into the top wall, well away from the paddles
+    let velocity = Vec2::new(1., 1.).normalize();
+    harness.place_ball(Vec2::new(0., HEIGHT / 2. - 50.), velocity);
+    let mut bounces = Vec::new();
+    harness.tick_until(CROSSING_TICKS, |harness| {
+        bounces.extend(harness.events::<WallBounce>());
+        !bounces.is_empty()
+    });
+    assert_eq!(bounces.len(), 1);
+    assert!(bounces[0].contact.y > 0.);
+    assert_eq!(harness.ball_velocity(), Vec2::new(velocity.x, -velocity.y));
+}
+
+#[test]
+fn paddles_stay_inside_the_arena() {
+    let mut harness = Harness::new();
+    let limit = HEIGHT / 2. - paddle::SIZE.y / 2.;
+
+    harness.press(KeyCode::ArrowUp);
+    harness.tick(100);
+    assert_eq!(harness.paddle_position(Side::Right).y, limit);
+    harness.release(KeyCode::ArrowUp);
+
+    harness.press(KeyCode::ArrowDown);
+    harness.tick(100);
+    assert_eq!(harness.paddle_position(Side::Right).y, -limit);
+    harness.release(KeyCode::ArrowDown);
+
+    harness.tick(10);
+    assert_eq!(harness.paddle_position(Side::Right).y, -limit);
+}
+
+#[test]
+fn reset_starts_a_fresh_match() {
+    let mut harness = Harness::new();
+    assert_eq!(harness.state(), GameState::Playing);
+    wait_for_serve(&mut harness);
+    score_against(&mut harness, Side::Right);
+    wait_for_serve(&mut harness);
+    harness.press(KeyCode::ArrowUp);
+    harness.tick(20);
+    harness.release(KeyCode::ArrowUp);
+    assert_ne!(harness.score(), (0, 0));
+    assert_ne!(harness.paddle_position(Side::Right).y, 0.);
+
+    harness.set_state(GameState::Reset);
+    harness.tick(1);
+    assert_eq!(harness.state(), GameState::Reset);
+    // Reset hands straight over to a new match
+    harness.tick(1);
+    assert_eq!(harness.state(), GameState::Playing);
+
+    assert_eq!(harness.score(), (0, 0));
+    assert_eq!(harness.ball_position(), Vec2::ZERO);
+    assert_eq!(harness.ball_velocity(), Vec2::ZERO);
+    assert_eq!(harness.paddle_position(Side::Left).y, 0.);
+    assert_eq!(harness.paddle_position(Side::Right).y, 0.);
+    // The first serve of a match always goes to the right
+    harness.events::<ServeStarted>();
+    assert_eq!(wait_for_serve(&mut harness), Side::Right);
+}
+
+#[test]
+fn score_command_ends_the_match_only_at_the_winning_score() {
+    let mut harness = Harness::new();
+    harness.run_command("score 10 9");
+    harness.tick(2);
+    assert_eq!(harness.score(), (10, 9));
+    assert!(harness.events::<MatchEnded>().is_empty());
+
+    let output = harness.run_command("score 11 0");
+    assert!(output.contains("match is over"), "{output}");
+    harness.tick(2);
+    let ended = harness.events::<MatchEnded>();
+    assert_eq!(ended.len(), 1);
+    assert_eq!(ended[0].winner, Side::Left);
+}